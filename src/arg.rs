@@ -0,0 +1,95 @@
+use std::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+
+use crate::r#type::Type;
+
+/// Defines a function argument, e.g. `mut buf: &mut [u8]` or
+/// `(a, b): (u8, u8)`.
+#[derive(Debug, Clone)]
+pub struct Arg {
+    /// The argument pattern, e.g. a name, `_` or a destructuring pattern
+    pattern: String,
+
+    /// Whether or not the binding is `mut`
+    r#mut: bool,
+
+    /// Argument type
+    ty: Type,
+
+    /// Argument attributes, e.g., `#[allow(unused)]`.
+    attributes: Vec<String>,
+}
+
+impl Arg {
+    /// Return a new argument binding the given pattern to the given type.
+    pub fn new<T>(pattern: &str, ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Arg {
+            pattern: pattern.to_string(),
+            r#mut: false,
+            ty: ty.into(),
+            attributes: vec![],
+        }
+    }
+
+    /// Returns the argument pattern.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns a reference to the argument type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Set whether the argument binding is `mut` or not.
+    ///
+    /// Only an argument whose pattern is a plain identifier can be `mut`.
+    pub fn set_mut(&mut self, r#mut: bool) -> &mut Self {
+        self.r#mut = r#mut;
+        self
+    }
+
+    /// Add an attribute to the argument.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Call `f` with the type paths used by the argument.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.ty.visit_paths(f);
+    }
+
+    /// Formats the argument using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for attr in self.attributes.iter() {
+            write!(fmt, "#[{}] ", attr)?;
+        }
+
+        if self.r#mut {
+            assert!(
+                is_ident(&self.pattern),
+                "only identifier patterns can be `mut`"
+            );
+
+            write!(fmt, "mut ")?;
+        }
+
+        write!(fmt, "{}: ", self.pattern)?;
+        self.ty.fmt(fmt)
+    }
+}
+
+/// Returns `true` if the pattern is a plain identifier, e.g. `buf` or
+/// `r#type`, rather than `_` or a destructuring pattern.
+fn is_ident(pattern: &str) -> bool {
+    let name = pattern.strip_prefix("r#").unwrap_or(pattern);
+
+    name != "_"
+        && name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
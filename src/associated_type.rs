@@ -1,17 +1,184 @@
+use std::fmt::{self, Write};
+
 use crate::bound::Bound;
+use crate::docs::Docs;
+use crate::formatter::{fmt_bound_rhs, fmt_generics, Formatter};
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
+
 use crate::r#type::Type;
 
-/// Defines an associated type.
+/// Defines an associated type, e.g. `type Item<'a>: Debug where Self: 'a;`.
 #[derive(Debug, Clone)]
-pub struct AssociatedType(pub Bound);
+pub struct AssociatedType {
+    /// Name of the associated type
+    name: String,
+
+    /// Associated type documentation
+    docs: Option<Docs>,
+
+    /// Associated type attributes, e.g., `#[cfg(feature = "std")]`.
+    attributes: Vec<String>,
+
+    /// Associated type generics
+    generics: Vec<GenericParam>,
+
+    /// Bounds on the associated type
+    bounds: Vec<TypeBound>,
+
+    /// Where bounds
+    where_bounds: Vec<Bound>,
+
+    /// The type, which acts as a default in traits
+    ty: Option<Type>,
+}
 
 impl AssociatedType {
+    /// Return a new associated type with the given name.
+    pub fn new(name: &str) -> Self {
+        AssociatedType {
+            name: name.to_string(),
+            docs: None,
+            attributes: vec![],
+            generics: vec![],
+            bounds: vec![],
+            where_bounds: vec![],
+            ty: None,
+        }
+    }
+
+    /// Returns the associated type name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the associated type documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an attribute to the associated type.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Add a generic to the associated type, e.g. `'a`.
+    pub fn generic(&mut self, name: &str) -> &mut Self {
+        for param in GenericParam::parse_list(name) {
+            GenericParam::push(&mut self.generics, param);
+        }
+        self
+    }
+
+    /// Push a generic parameter to the associated type.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        GenericParam::push(&mut self.generics, param);
+        self
+    }
+
     /// Add a bound to the associated type.
     pub fn bound<T>(&mut self, ty: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        TypeBound::push(&mut self.bounds, ty.into());
+        self
+    }
+
+    /// Add a `where` bound to the associated type, e.g. `Self: 'a`.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
+    pub fn where_bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        let mut bound = Bound::parse_lhs(name);
+        bound.bound(ty);
+        self.push_where_bound(bound)
+    }
+
+    /// Push a `where` predicate to the associated type.
+    pub fn push_where_bound(&mut self, bound: Bound) -> &mut Self {
+        Bound::push(&mut self.where_bounds, bound);
+        self
+    }
+
+    /// Set the type of the associated type.
+    ///
+    /// In a trait, this is the default used by implementors that do not set
+    /// the type themselves.
+    pub fn ty<T>(&mut self, ty: T) -> &mut Self
     where
         T: Into<Type>,
     {
-        self.0.bound.push(ty.into());
+        self.ty = Some(ty.into());
         self
     }
+
+    /// Call `f` with the type paths used by the associated type, along with
+    /// the names of its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for param in &mut self.generics {
+            param.visit_paths(f);
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_paths(f);
+        }
+
+        for bound in &mut self.where_bounds {
+            bound.visit_paths(f);
+        }
+
+        if let Some(ref mut ty) = self.ty {
+            ty.visit_paths(f);
+        }
+    }
+
+    /// Formats the associated type using the given formatter.
+    pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        write!(fmt, "type {}", self.name)?;
+        fmt_generics(&self.generics, fmt)?;
+
+        if !self.bounds.is_empty() {
+            assert!(is_trait, "associated types in impls cannot have bounds");
+
+            write!(fmt, ": ")?;
+            fmt_bound_rhs(&self.bounds, fmt)?;
+        }
+
+        match self.ty {
+            Some(ref ty) => {
+                write!(fmt, " = ")?;
+                ty.fmt(fmt)?;
+            }
+            None => {
+                if !is_trait {
+                    panic!("associated types in impls must have a type");
+                }
+            }
+        }
+
+        for (i, bound) in self.where_bounds.iter().enumerate() {
+            if i == 0 {
+                write!(fmt, " where ")?;
+            } else {
+                write!(fmt, ", ")?;
+            }
+
+            bound.fmt(fmt)?;
+        }
+
+        writeln!(fmt, ";")
+    }
 }
@@ -1,7 +1,125 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::parse::{ParseError, Parser};
+use crate::type_bound::TypeBound;
+
 use crate::r#type::Type;
 
-#[derive(Debug, Clone)]
+/// Defines a `where` clause predicate, e.g. `T: Clone + Send + 'static` or
+/// `for<'a> F: Fn(&'a str)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bound {
-    pub name: String,
-    pub bound: Vec<Type>,
+    /// Lifetimes introduced by a `for<...>` binder
+    lifetimes: Vec<String>,
+
+    /// The bounded type or lifetime
+    lhs: Lhs,
+
+    /// The bounds
+    bound: Vec<TypeBound>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lhs {
+    Type(Type),
+    Lifetime(String),
+}
+
+impl Bound {
+    /// Return a new predicate bounding the given type, e.g. `Vec<T>`.
+    pub fn new<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Bound {
+            lifetimes: vec![],
+            lhs: Lhs::Type(ty.into()),
+            bound: vec![],
+        }
+    }
+
+    /// Return a new predicate bounding the given lifetime, e.g. `'a: 'b`.
+    pub fn lifetime(lifetime: &str) -> Self {
+        Bound {
+            lifetimes: vec![],
+            lhs: Lhs::Lifetime(lifetime.to_string()),
+            bound: vec![],
+        }
+    }
+
+    /// Add a lifetime to the `for<...>` binder of the predicate.
+    pub fn for_lifetime(&mut self, lifetime: &str) -> &mut Self {
+        self.lifetimes.push(lifetime.to_string());
+        self
+    }
+
+    /// Add a bound to the predicate.
+    pub fn bound<T>(&mut self, bound: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        TypeBound::push(&mut self.bound, bound.into());
+        self
+    }
+
+    /// Parse the left-hand side of a predicate, e.g. `for<'a> F`.
+    ///
+    /// If it cannot be parsed, it is used verbatim.
+    pub(crate) fn parse_lhs(src: &str) -> Self {
+        Parser::parse_all(src, |p| p.where_predicate_lhs())
+            .unwrap_or_else(|_| Bound::new(Type::new_path(src.to_string())))
+    }
+
+    /// Push a predicate, merging its bounds into an existing predicate for the
+    /// same type or lifetime.
+    pub(crate) fn push(bounds: &mut Vec<Bound>, predicate: Bound) {
+        let existing = bounds
+            .iter_mut()
+            .find(|b| b.lhs == predicate.lhs && b.lifetimes == predicate.lifetimes);
+
+        match existing {
+            Some(existing) => existing.bound.extend(predicate.bound),
+            None => bounds.push(predicate),
+        }
+    }
+
+    /// Call `f` with the path of every named type within the predicate.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        if let Lhs::Type(ref mut ty) = self.lhs {
+            ty.visit_paths(f);
+        }
+
+        for bound in &mut self.bound {
+            bound.visit_paths(f);
+        }
+    }
+
+    /// Formats the predicate using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if !self.lifetimes.is_empty() {
+            write!(fmt, "for<{}> ", self.lifetimes.join(", "))?;
+        }
+
+        match self.lhs {
+            Lhs::Type(ref ty) => ty.fmt(fmt)?,
+            Lhs::Lifetime(ref lifetime) => write!(fmt, "{}", lifetime)?,
+        }
+
+        if !self.bound.is_empty() {
+            write!(fmt, ": ")?;
+            fmt_bound_rhs(&self.bound, fmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Bound {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        Parser::parse_all(src, |p| p.where_predicate())
+    }
 }
@@ -0,0 +1,126 @@
+use std::fmt::{self, Write};
+
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+
+use crate::r#type::Type;
+
+/// Defines a `const` item.
+#[derive(Debug, Clone)]
+pub struct Const {
+    /// Name of the constant
+    name: String,
+
+    /// Constant documentation
+    docs: Option<Docs>,
+
+    /// Constant visibility
+    vis: Option<String>,
+
+    /// Constant attributes, e.g., `#[cfg(unix)]`.
+    attributes: Vec<String>,
+
+    /// Constant type
+    ty: Type,
+
+    /// Initializer expression
+    value: Option<String>,
+}
+
+impl Const {
+    /// Return a new constant definition with the provided name and type.
+    pub fn new<T>(name: &str, ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Const {
+            name: name.to_string(),
+            docs: None,
+            vis: None,
+            attributes: vec![],
+            ty: ty.into(),
+            value: None,
+        }
+    }
+
+    /// Returns the constant name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a reference to the constant type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Set the constant documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Set the constant visibility.
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add an attribute to the constant.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Set the initializer expression of the constant.
+    ///
+    /// The value is optional for associated constants in traits, in which case
+    /// implementors must provide it.
+    pub fn value<T>(&mut self, value: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.value = Some(value.to_string());
+        self
+    }
+
+    /// Call `f` with the type paths used by the const.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.ty.visit_paths(f);
+    }
+
+    /// Formats the constant using the given formatter.
+    pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        if is_trait {
+            assert!(
+                self.vis.is_none(),
+                "trait consts do not have visibility modifiers"
+            );
+        }
+
+        if let Some(ref vis) = self.vis {
+            write!(fmt, "{} ", vis)?;
+        }
+
+        write!(fmt, "const {}: ", self.name)?;
+        self.ty.fmt(fmt)?;
+
+        match self.value {
+            Some(ref value) => writeln!(fmt, " = {};", value),
+            None => {
+                if !is_trait {
+                    panic!("consts must have a value outside of traits");
+                }
+
+                writeln!(fmt, ";")
+            }
+        }
+    }
+}
@@ -16,7 +16,20 @@ impl Docs {
 
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for line in self.docs.lines() {
-            write!(fmt, "/// {}\n", line)?;
+            writeln!(fmt, "/// {}", line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats the docs as inner doc comments, e.g. `//! docs`.
+    pub fn fmt_inner(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for line in self.docs.lines() {
+            if line.is_empty() {
+                writeln!(fmt, "//!")?;
+            } else {
+                writeln!(fmt, "//! {}", line)?;
+            }
         }
 
         Ok(())
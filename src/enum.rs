@@ -1,6 +1,9 @@
 use std::fmt;
 
+use crate::bound::Bound;
 use crate::formatter::Formatter;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 use crate::type_def::TypeDef;
 use crate::variant::Variant;
 
@@ -22,6 +25,11 @@ impl Enum {
         }
     }
 
+    /// Returns the enum name.
+    pub fn name(&self) -> &str {
+        self.type_def.name()
+    }
+
     /// Returns a reference to the type.
     pub fn ty(&self) -> &Type {
         &self.type_def.ty
@@ -35,19 +43,33 @@ impl Enum {
 
     /// Add a generic to the enum.
     pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.type_def.ty.generic(name);
+        self.type_def.generic(name);
+        self
+    }
+
+    /// Push a generic parameter to the enum.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        self.type_def.push_generic(param);
         self
     }
 
     /// Add a `where` bound to the enum.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
     pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
         self.type_def.bound(name, ty);
         self
     }
 
+    /// Push a `where` predicate to the enum.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        self.type_def.push_bound(bound);
+        self
+    }
+
     /// Set the enum documentation.
     pub fn doc(&mut self, docs: &str) -> &mut Self {
         self.type_def.doc(docs);
@@ -84,6 +106,16 @@ impl Enum {
         self
     }
 
+    /// Call `f` with the type paths used by the enum, along with the names of
+    /// its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.type_def.visit_paths(f);
+
+        for variant in &mut self.variants {
+            variant.visit_paths(f);
+        }
+    }
+
     /// Formats the enum using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         self.type_def.fmt_head("enum", &[], fmt)?;
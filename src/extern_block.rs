@@ -0,0 +1,169 @@
+use std::fmt::{self, Write};
+
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+use crate::function::Function;
+
+use crate::r#static::Static;
+use crate::r#type::Type;
+
+/// Defines an `extern` block declaring foreign functions and statics.
+#[derive(Debug, Clone)]
+pub struct ExternBlock {
+    /// The ABI of the block, e.g. `"C"`
+    abi: String,
+
+    /// Block documentation
+    docs: Option<Docs>,
+
+    /// Block attributes, e.g., `#[link(name = "foo")]`.
+    attributes: Vec<String>,
+
+    /// Whether or not this block is `unsafe extern`
+    r#unsafe: bool,
+
+    /// Foreign items
+    items: Vec<ForeignItem>,
+}
+
+#[derive(Debug, Clone)]
+enum ForeignItem {
+    Function(Function),
+    Static(Static),
+}
+
+impl ExternBlock {
+    /// Return a new `extern` block using the given ABI, e.g. `"C"`.
+    pub fn new(abi: &str) -> Self {
+        ExternBlock {
+            abi: abi.to_string(),
+            docs: None,
+            attributes: vec![],
+            r#unsafe: false,
+            items: vec![],
+        }
+    }
+
+    /// Returns the ABI of the block.
+    pub fn abi(&self) -> &str {
+        &self.abi
+    }
+
+    /// Set the block documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an attribute to the block.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Link against a native library, adding `#[link(name = "...")]`.
+    pub fn link(&mut self, name: &str) -> &mut Self {
+        self.attr(&format!("link(name = \"{}\")", name))
+    }
+
+    /// Set whether this block is declared `unsafe extern` or not, as required
+    /// by the 2024 edition.
+    ///
+    /// Items of an `unsafe extern` block may be declared `safe` or `unsafe`,
+    /// see [`Function::set_safe`] and [`Static::set_safe`].
+    ///
+    /// [`Function::set_safe`]: struct.Function.html#method.set_safe
+    /// [`Static::set_safe`]: struct.Static.html#method.set_safe
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Push a new foreign function declaration, returning a mutable reference
+    /// to it.
+    pub fn new_fn(&mut self, name: &str) -> &mut Function {
+        let mut func = Function::new(name);
+        func.body = None;
+
+        self.push_fn(func);
+
+        match *self.items.last_mut().unwrap() {
+            ForeignItem::Function(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a foreign function declaration.
+    pub fn push_fn(&mut self, item: Function) -> &mut Self {
+        self.items.push(ForeignItem::Function(item));
+        self
+    }
+
+    /// Push a new foreign static declaration, returning a mutable reference
+    /// to it.
+    pub fn new_static<T>(&mut self, name: &str, ty: T) -> &mut Static
+    where
+        T: Into<Type>,
+    {
+        self.push_static(Static::new(name, ty));
+
+        match *self.items.last_mut().unwrap() {
+            ForeignItem::Static(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a foreign static declaration.
+    pub fn push_static(&mut self, item: Static) -> &mut Self {
+        self.items.push(ForeignItem::Static(item));
+        self
+    }
+
+    /// Call `f` with the type paths used by the foreign items.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for item in &mut self.items {
+            match *item {
+                ForeignItem::Function(ref mut func) => func.visit_paths(f),
+                ForeignItem::Static(ref mut item) => item.visit_paths(f),
+            }
+        }
+    }
+
+    /// Formats the block using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
+        }
+
+        write!(fmt, "extern \"{}\"", self.abi)?;
+
+        fmt.block(|fmt| {
+            for item in &self.items {
+                let has_safety = match *item {
+                    ForeignItem::Function(ref v) => v.has_safety(),
+                    ForeignItem::Static(ref v) => v.has_safety(),
+                };
+
+                assert!(
+                    self.r#unsafe || !has_safety,
+                    "only items of an `unsafe extern` block can be declared `safe` or `unsafe`"
+                );
+
+                match *item {
+                    ForeignItem::Function(ref v) => v.fmt_foreign(fmt)?,
+                    ForeignItem::Static(ref v) => v.fmt(true, fmt)?,
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -57,30 +57,29 @@ impl Fields {
         self
     }
 
+    /// Call `f` with the type paths used by the fields.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match *self {
+            Fields::Tuple(ref mut tys) => {
+                for ty in tys {
+                    ty.visit_paths(f);
+                }
+            }
+            Fields::Named(ref mut fields) => {
+                for field in fields {
+                    field.ty.visit_paths(f);
+                }
+            }
+            Fields::Empty => {}
+        }
+    }
+
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         match *self {
             Fields::Named(ref fields) => {
                 assert!(!fields.is_empty());
 
-                fmt.block(|fmt| {
-                    for f in fields {
-                        if !f.documentation.is_empty() {
-                            for doc in &f.documentation {
-                                write!(fmt, "/// {}\n", doc)?;
-                            }
-                        }
-                        if !f.annotation.is_empty() {
-                            for ann in &f.annotation {
-                                write!(fmt, "{}\n", ann)?;
-                            }
-                        }
-                        write!(fmt, "{}: ", f.name)?;
-                        f.ty.fmt(fmt)?;
-                        write!(fmt, ",\n")?;
-                    }
-
-                    Ok(())
-                })?;
+                fmt.block(|fmt| fmt_named(fields, fmt))?;
             }
             Fields::Tuple(ref tys) => {
                 assert!(!tys.is_empty());
@@ -101,4 +100,39 @@ impl Fields {
 
         Ok(())
     }
+
+    /// Formats the fields of an enum variant, which are not followed by a
+    /// new line so that the variant can be terminated with a comma.
+    pub(crate) fn fmt_variant(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Fields::Named(ref fields) => {
+                assert!(!fields.is_empty());
+
+                writeln!(fmt, " {{")?;
+                fmt.indent(|fmt| fmt_named(fields, fmt))?;
+                write!(fmt, "}}")
+            }
+            _ => self.fmt(fmt),
+        }
+    }
+}
+
+fn fmt_named(fields: &[Field], fmt: &mut Formatter<'_>) -> fmt::Result {
+    for f in fields {
+        if !f.documentation.is_empty() {
+            for doc in &f.documentation {
+                writeln!(fmt, "/// {}", doc)?;
+            }
+        }
+        if !f.annotation.is_empty() {
+            for ann in &f.annotation {
+                writeln!(fmt, "{}", ann)?;
+            }
+        }
+        write!(fmt, "{}: ", f.name)?;
+        f.ty.fmt(fmt)?;
+        writeln!(fmt, ",")?;
+    }
+
+    Ok(())
 }
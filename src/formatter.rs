@@ -1,8 +1,8 @@
 use std::fmt::{self, Write};
 
 use crate::bound::Bound;
-
-use crate::r#type::Type;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 
 const DEFAULT_INDENT: usize = 4;
 
@@ -38,9 +38,9 @@ impl<'a> Formatter<'a> {
             write!(self, " ")?;
         }
 
-        write!(self, "{{\n")?;
+        writeln!(self, "{{")?;
         self.indent(f)?;
-        write!(self, "}}\n")?;
+        writeln!(self, "}}")?;
         Ok(())
     }
 
@@ -62,7 +62,7 @@ impl<'a> Formatter<'a> {
 
     fn push_spaces(&mut self) {
         for _ in 0..self.spaces {
-            self.dst.push_str(" ");
+            self.dst.push(' ');
         }
     }
 }
@@ -74,7 +74,7 @@ impl<'a> fmt::Write for Formatter<'a> {
 
         for line in s.lines() {
             if !first {
-                self.dst.push_str("\n");
+                self.dst.push('\n');
             }
 
             first = false;
@@ -92,7 +92,7 @@ impl<'a> fmt::Write for Formatter<'a> {
         }
 
         if s.as_bytes().last() == Some(&b'\n') {
-            self.dst.push_str("\n");
+            self.dst.push('\n');
         }
 
         Ok(())
@@ -100,15 +100,15 @@ impl<'a> fmt::Write for Formatter<'a> {
 }
 
 /// Format generics.
-pub fn fmt_generics(generics: &[String], fmt: &mut Formatter<'_>) -> fmt::Result {
+pub fn fmt_generics(generics: &[GenericParam], fmt: &mut Formatter<'_>) -> fmt::Result {
     if !generics.is_empty() {
         write!(fmt, "<")?;
 
-        for (i, ty) in generics.iter().enumerate() {
+        for (i, param) in generics.iter().enumerate() {
             if i != 0 {
                 write!(fmt, ", ")?
             }
-            write!(fmt, "{}", ty)?;
+            param.fmt(fmt)?;
         }
 
         write!(fmt, ">")?;
@@ -120,17 +120,17 @@ pub fn fmt_generics(generics: &[String], fmt: &mut Formatter<'_>) -> fmt::Result
 /// Format generic bounds.
 pub fn fmt_bounds(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
     if !bounds.is_empty() {
-        write!(fmt, "\n")?;
+        writeln!(fmt)?;
 
         // Write first bound
-        write!(fmt, "where {}: ", bounds[0].name)?;
-        fmt_bound_rhs(&bounds[0].bound, fmt)?;
-        write!(fmt, ",\n")?;
+        write!(fmt, "where ")?;
+        bounds[0].fmt(fmt)?;
+        writeln!(fmt, ",")?;
 
         for bound in &bounds[1..] {
-            write!(fmt, "      {}: ", bound.name)?;
-            fmt_bound_rhs(&bound.bound, fmt)?;
-            write!(fmt, ",\n")?;
+            write!(fmt, "      ")?;
+            bound.fmt(fmt)?;
+            writeln!(fmt, ",")?;
         }
     }
 
@@ -138,12 +138,12 @@ pub fn fmt_bounds(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
 }
 
 /// Format multiple generic bounds.
-pub fn fmt_bound_rhs(tys: &[Type], fmt: &mut Formatter<'_>) -> fmt::Result {
-    for (i, ty) in tys.iter().enumerate() {
+pub fn fmt_bound_rhs(bounds: &[TypeBound], fmt: &mut Formatter<'_>) -> fmt::Result {
+    for (i, bound) in bounds.iter().enumerate() {
         if i != 0 {
             write!(fmt, " + ")?
         }
-        ty.fmt(fmt)?;
+        bound.fmt(fmt)?;
     }
 
     Ok(())
@@ -1,12 +1,15 @@
 use std::fmt::{self, Write};
 
+use crate::arg::Arg;
 use crate::block::Block;
 use crate::body::Body;
 use crate::bound::Bound;
 use crate::docs::Docs;
-use crate::field::Field;
 use crate::formatter::Formatter;
 use crate::formatter::{fmt_bounds, fmt_generics};
+use crate::generic_param::GenericParam;
+use crate::receiver::Receiver;
+use crate::type_bound::TypeBound;
 
 use crate::r#type::Type;
 
@@ -26,13 +29,13 @@ pub struct Function {
     vis: Option<String>,
 
     /// Function generics
-    generics: Vec<String>,
+    generics: Vec<GenericParam>,
 
-    /// If the function takes `&self` or `&mut self`
-    arg_self: Option<String>,
+    /// The `self` receiver of the function, if any
+    arg_self: Option<Receiver>,
 
     /// Function arguments
-    args: Vec<Field>,
+    args: Vec<Arg>,
 
     /// Return type
     ret: Option<Type>,
@@ -51,6 +54,18 @@ pub struct Function {
 
     /// Whether or not this function is `async` or not
     r#async: bool,
+
+    /// Whether or not this function is `unsafe` or not
+    r#unsafe: bool,
+
+    /// Whether or not this foreign function is `safe` or not
+    safe: bool,
+
+    /// Whether or not this function is `const` or not
+    r#const: bool,
+
+    /// Whether or not this function takes a variable number of arguments
+    variadic: bool,
 }
 
 impl Function {
@@ -70,9 +85,18 @@ impl Function {
             attributes: vec![],
             extern_abi: None,
             r#async: false,
+            r#unsafe: false,
+            safe: false,
+            r#const: false,
+            variadic: false,
         }
     }
 
+    /// Returns the function name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Set the function documentation.
     pub fn doc(&mut self, docs: &str) -> &mut Self {
         self.docs = Some(Docs::new(docs));
@@ -97,28 +121,59 @@ impl Function {
         self
     }
 
+    /// Set whether this function is unsafe or not
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Set whether this foreign function is declared `safe` or not.
+    ///
+    /// Only functions in an `unsafe extern` block can be declared `safe`.
+    pub fn set_safe(&mut self, safe: bool) -> &mut Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Set whether this function is const or not
+    pub fn set_const(&mut self, r#const: bool) -> &mut Self {
+        self.r#const = r#const;
+        self
+    }
+
     /// Add a generic to the function.
     pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.generics.push(name.to_string());
+        for param in GenericParam::parse_list(name) {
+            GenericParam::push(&mut self.generics, param);
+        }
+        self
+    }
+
+    /// Push a generic parameter to the function.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        GenericParam::push(&mut self.generics, param);
+        self
+    }
+
+    /// Set the `self` receiver of the function.
+    pub fn receiver(&mut self, receiver: Receiver) -> &mut Self {
+        self.arg_self = Some(receiver);
         self
     }
 
     /// Add `self` as a function argument.
     pub fn arg_self(&mut self) -> &mut Self {
-        self.arg_self = Some("self".to_string());
-        self
+        self.receiver(Receiver::value())
     }
 
     /// Add `&self` as a function argument.
     pub fn arg_ref_self(&mut self) -> &mut Self {
-        self.arg_self = Some("&self".to_string());
-        self
+        self.receiver(Receiver::reference())
     }
 
     /// Add `&mut self` as a function argument.
     pub fn arg_mut_self(&mut self) -> &mut Self {
-        self.arg_self = Some("&mut self".to_string());
-        self
+        self.receiver(Receiver::mut_reference())
     }
 
     /// Add a function argument.
@@ -126,16 +181,32 @@ impl Function {
     where
         T: Into<Type>,
     {
-        self.args.push(Field {
-            name: name.to_string(),
-            ty: ty.into(),
-            // While a `Field` is used here, both `documentation`
-            // and `annotation` does not make sense for function arguments.
-            // Simply use empty strings.
-            documentation: Vec::new(),
-            annotation: Vec::new(),
-        });
+        self.push_arg(Arg::new(name, ty))
+    }
 
+    /// Push a new function argument, returning a mutable reference to it.
+    ///
+    /// The argument is bound to the given pattern, e.g. `_` or `(a, b)`.
+    pub fn new_arg<T>(&mut self, pattern: &str, ty: T) -> &mut Arg
+    where
+        T: Into<Type>,
+    {
+        self.push_arg(Arg::new(pattern, ty));
+        self.args.last_mut().unwrap()
+    }
+
+    /// Push a function argument.
+    pub fn push_arg(&mut self, arg: Arg) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Set whether this function is variadic or not, i.e. ends its argument
+    /// list with `...`.
+    ///
+    /// Only foreign functions declared in an `extern` block may be variadic.
+    pub fn set_variadic(&mut self, variadic: bool) -> &mut Self {
+        self.variadic = variadic;
         self
     }
 
@@ -149,17 +220,33 @@ impl Function {
     }
 
     /// Add a `where` bound to the function.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
     pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
-        self.bounds.push(Bound {
-            name: name.to_string(),
-            bound: vec![ty.into()],
-        });
+        let mut bound = Bound::parse_lhs(name);
+        bound.bound(ty);
+        self.push_bound(bound)
+    }
+
+    /// Push a `where` predicate to the function.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        Bound::push(&mut self.bounds, bound);
         self
     }
 
+    /// Add a `where Self: Sized` bound to the function.
+    ///
+    /// This excludes a trait method from the trait's vtable, keeping the
+    /// trait usable as a trait object.
+    pub fn self_sized(&mut self) -> &mut Self {
+        let mut bound = Bound::new(Type::self_type());
+        bound.bound("Sized");
+        self.push_bound(bound)
+    }
+
     /// Push a line to the function implementation.
     pub fn line<T>(&mut self, line: T) -> &mut Self
     where
@@ -208,18 +295,94 @@ impl Function {
         self
     }
 
+    /// Call `f` with the type paths used by the function signature, along
+    /// with the names of its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for param in &mut self.generics {
+            param.visit_paths(f);
+        }
+
+        if let Some(ref mut receiver) = self.arg_self {
+            receiver.visit_paths(f);
+        }
+
+        for arg in &mut self.args {
+            arg.visit_paths(f);
+        }
+
+        if let Some(ref mut ret) = self.ret {
+            ret.visit_paths(f);
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_paths(f);
+        }
+    }
+
     /// Formats the function using the given formatter.
     pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
+        assert!(!self.safe, "only foreign fns can be declared `safe`");
+
+        self.fmt_head(is_trait, fmt)?;
+
+        match self.body {
+            Some(ref body) => {
+                fmt_bounds(&self.bounds, fmt)?;
+
+                fmt.block(|fmt| {
+                    for b in body {
+                        b.fmt(fmt)?;
+                    }
+
+                    Ok(())
+                })
+            }
+            None => {
+                if !is_trait {
+                    panic!("impl blocks must define fn bodies");
+                }
+
+                self.fmt_declaration_end(fmt)
+            }
+        }
+    }
+
+    /// Formats the function as a declaration in an `extern` block.
+    pub(crate) fn fmt_foreign(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref body) = self.body {
+            assert!(body.is_empty(), "foreign fns cannot have bodies");
+        }
+
+        assert!(
+            self.extern_abi.is_none(),
+            "foreign fns take the ABI of their extern block"
+        );
+
+        assert!(
+            !(self.safe && self.r#unsafe),
+            "fns cannot be both `safe` and `unsafe`"
+        );
+
+        self.fmt_head(false, fmt)?;
+        self.fmt_declaration_end(fmt)
+    }
+
+    /// Returns `true` if the function is declared `safe` or `unsafe`.
+    pub(crate) fn has_safety(&self) -> bool {
+        self.safe || self.r#unsafe
+    }
+
+    fn fmt_head(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
         if let Some(ref docs) = self.docs {
             docs.fmt(fmt)?;
         }
 
         if let Some(ref allow) = self.allow {
-            write!(fmt, "#[allow({})]\n", allow)?;
+            writeln!(fmt, "#[allow({})]", allow)?;
         }
 
         for attr in self.attributes.iter() {
-            write!(fmt, "#[{}]\n", attr)?;
+            writeln!(fmt, "#[{}]", attr)?;
         }
 
         if is_trait {
@@ -233,21 +396,33 @@ impl Function {
             write!(fmt, "{} ", vis)?;
         }
 
-        if let Some(ref extern_abi) = self.extern_abi {
-            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
+        if self.r#const {
+            write!(fmt, "const ")?;
         }
 
         if self.r#async {
             write!(fmt, "async ")?;
         }
 
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
+        }
+
+        if self.safe {
+            write!(fmt, "safe ")?;
+        }
+
+        if let Some(ref extern_abi) = self.extern_abi {
+            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
+        }
+
         write!(fmt, "fn {}", self.name)?;
         fmt_generics(&self.generics, fmt)?;
 
         write!(fmt, "(")?;
 
-        if let Some(ref s) = self.arg_self {
-            write!(fmt, "{}", s)?;
+        if let Some(ref receiver) = self.arg_self {
+            receiver.fmt(fmt)?;
         }
 
         for (i, arg) in self.args.iter().enumerate() {
@@ -255,8 +430,15 @@ impl Function {
                 write!(fmt, ", ")?;
             }
 
-            write!(fmt, "{}: ", arg.name)?;
-            arg.ty.fmt(fmt)?;
+            arg.fmt(fmt)?;
+        }
+
+        if self.variadic {
+            if self.args.is_empty() && self.arg_self.is_none() {
+                write!(fmt, "...")?;
+            } else {
+                write!(fmt, ", ...")?;
+            }
         }
 
         write!(fmt, ")")?;
@@ -266,23 +448,26 @@ impl Function {
             ret.fmt(fmt)?;
         }
 
-        fmt_bounds(&self.bounds, fmt)?;
+        Ok(())
+    }
 
-        match self.body {
-            Some(ref body) => fmt.block(|fmt| {
-                for b in body {
-                    b.fmt(fmt)?;
-                }
+    /// Formats the end of a function without a body, with each `where`
+    /// predicate on its own line and the last one followed by the `;`, as
+    /// rustfmt does.
+    fn fmt_declaration_end(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if !self.bounds.is_empty() {
+            write!(fmt, "\nwhere")?;
 
-                Ok(())
-            }),
-            None => {
-                if !is_trait {
-                    panic!("impl blocks must define fn bodies");
+            for (i, bound) in self.bounds.iter().enumerate() {
+                if i != 0 {
+                    write!(fmt, ",")?;
                 }
 
-                write!(fmt, ";\n")
+                write!(fmt, "\n    ")?;
+                bound.fmt(fmt)?;
             }
         }
+
+        writeln!(fmt, ";")
     }
 }
@@ -0,0 +1,216 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::parse::{ParseError, Parser};
+use crate::type_bound::TypeBound;
+
+use crate::r#type::{GenericArg, Type};
+
+/// Defines a generic parameter, e.g. `'a`, `T: Clone = String` or
+/// `const N: usize = 4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericParam {
+    name: String,
+    kind: Kind,
+    bounds: Vec<TypeBound>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Lifetime,
+    Type {
+        default: Option<GenericArg>,
+    },
+    Const {
+        ty: Type,
+        default: Option<GenericArg>,
+    },
+    /// A parameter that could not be parsed, included verbatim.
+    Raw,
+}
+
+impl GenericParam {
+    /// Return a new type parameter with the given name.
+    pub fn new(name: &str) -> Self {
+        GenericParam {
+            name: name.to_string(),
+            kind: Kind::Type { default: None },
+            bounds: vec![],
+        }
+    }
+
+    /// Return a new lifetime parameter with the given name, e.g. `'a`.
+    pub fn lifetime(name: &str) -> Self {
+        GenericParam {
+            name: name.to_string(),
+            kind: Kind::Lifetime,
+            bounds: vec![],
+        }
+    }
+
+    /// Return a new const parameter with the given name and type, e.g.
+    /// `const N: usize`.
+    pub fn constant<T>(name: &str, ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        GenericParam {
+            name: name.to_string(),
+            kind: Kind::Const {
+                ty: ty.into(),
+                default: None,
+            },
+            bounds: vec![],
+        }
+    }
+
+    /// Returns the parameter name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add an inline bound to the parameter, e.g. `T: Clone` or `'a: 'b`.
+    pub fn bound<T>(&mut self, bound: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        TypeBound::push(&mut self.bounds, bound.into());
+        self
+    }
+
+    /// Set the default of a type or const parameter, e.g. `T = String` or
+    /// `const N: usize = 4`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameter is a lifetime.
+    pub fn default<T>(&mut self, arg: T) -> &mut Self
+    where
+        T: Into<GenericArg>,
+    {
+        match self.kind {
+            Kind::Type { ref mut default }
+            | Kind::Const {
+                ref mut default, ..
+            } => *default = Some(arg.into()),
+            _ => panic!("lifetime parameters cannot have a default"),
+        }
+
+        self
+    }
+
+    /// Parse a comma separated list of generic parameters.
+    ///
+    /// If the list cannot be parsed, it is kept as a single verbatim
+    /// parameter.
+    pub(crate) fn parse_list(src: &str) -> Vec<GenericParam> {
+        Parser::parse_all(src, |p| p.generic_params()).unwrap_or_else(|_| {
+            vec![GenericParam {
+                name: src.to_string(),
+                kind: Kind::Raw,
+                bounds: vec![],
+            }]
+        })
+    }
+
+    /// Push a parameter, keeping the list in the order required by Rust:
+    /// lifetimes first, followed by parameters without and then with
+    /// defaults.
+    pub(crate) fn push(generics: &mut Vec<GenericParam>, param: GenericParam) {
+        generics.push(param);
+        generics.sort_by_key(GenericParam::order);
+    }
+
+    fn order(&self) -> usize {
+        match self.kind {
+            Kind::Lifetime => 0,
+            Kind::Type { default: Some(_) }
+            | Kind::Const {
+                default: Some(_), ..
+            } => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the argument referring to this parameter, e.g. the `T` in
+    /// `Foo<T>` for a struct declared as `struct Foo<T: Clone>`.
+    pub(crate) fn to_arg(&self) -> GenericArg {
+        match self.kind {
+            Kind::Lifetime => GenericArg::lifetime(&self.name),
+            Kind::Type { .. } => GenericArg::from(Type::new(&self.name)),
+            Kind::Const { .. } => GenericArg::constant(&self.name),
+            Kind::Raw => GenericArg::from(Type::new_path(self.name.clone())),
+        }
+    }
+
+    /// Call `f` with the name of a type or const parameter, which shadows
+    /// imported types of the same name, and with the type paths used by the
+    /// parameter.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match self.kind {
+            Kind::Type { ref mut default } => {
+                f(&mut self.name);
+
+                if let Some(ref mut default) = *default {
+                    default.visit_paths(f);
+                }
+            }
+            Kind::Const {
+                ref mut ty,
+                ref mut default,
+            } => {
+                f(&mut self.name);
+                ty.visit_paths(f);
+
+                if let Some(ref mut default) = *default {
+                    default.visit_paths(f);
+                }
+            }
+            Kind::Lifetime | Kind::Raw => {}
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_paths(f);
+        }
+    }
+
+    /// Formats the parameter using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Kind::Const { ref ty, .. } = self.kind {
+            write!(fmt, "const {}: ", self.name)?;
+            ty.fmt(fmt)?;
+        } else {
+            write!(fmt, "{}", self.name)?;
+        }
+
+        if !self.bounds.is_empty() {
+            write!(fmt, ": ")?;
+            fmt_bound_rhs(&self.bounds, fmt)?;
+        }
+
+        match self.kind {
+            Kind::Type {
+                default: Some(ref default),
+            }
+            | Kind::Const {
+                default: Some(ref default),
+                ..
+            } => {
+                write!(fmt, " = ")?;
+                default.fmt(fmt)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for GenericParam {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        Parser::parse_all(src, |p| p.generic_param())
+    }
+}
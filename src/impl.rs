@@ -1,11 +1,15 @@
 use std::fmt::{self, Write};
 
+use crate::associated_type::AssociatedType;
 use crate::bound::Bound;
-use crate::field::Field;
+use crate::docs::Docs;
 use crate::formatter::{fmt_bounds, fmt_generics, Formatter};
 use crate::function::Function;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 
-use crate::r#type::Type;
+use crate::r#const::Const;
+use crate::r#type::{GenericArg, Type};
 
 /// Defines an impl block.
 #[derive(Debug, Clone)]
@@ -14,13 +18,16 @@ pub struct Impl {
     target: Type,
 
     /// Impl level generics
-    generics: Vec<String>,
+    generics: Vec<GenericParam>,
 
     /// If implementing a trait
     impl_trait: Option<Type>,
 
     /// Associated types
-    assoc_tys: Vec<Field>,
+    assoc_tys: Vec<AssociatedType>,
+
+    /// Associated constants
+    consts: Vec<Const>,
 
     /// Bounds
     bounds: Vec<Bound>,
@@ -28,6 +35,18 @@ pub struct Impl {
     fns: Vec<Function>,
 
     macros: Vec<String>,
+
+    /// Impl documentation
+    docs: Option<Docs>,
+
+    /// Impl attributes, e.g., `#[automatically_derived]`.
+    attributes: Vec<String>,
+
+    /// Whether or not the impl is `unsafe`
+    r#unsafe: bool,
+
+    /// Whether or not the impl is negative, e.g. `impl !Sync for Foo`
+    negative: bool,
 }
 
 impl Impl {
@@ -41,24 +60,54 @@ impl Impl {
             generics: vec![],
             impl_trait: None,
             assoc_tys: vec![],
+            consts: vec![],
             bounds: vec![],
             fns: vec![],
             macros: vec![],
+            docs: None,
+            attributes: vec![],
+            r#unsafe: false,
+            negative: false,
         }
     }
 
+    /// Returns a reference to the type being implemented.
+    pub fn target(&self) -> &Type {
+        &self.target
+    }
+
+    /// Returns a reference to the implemented trait, if any.
+    pub fn trait_type(&self) -> Option<&Type> {
+        self.impl_trait.as_ref()
+    }
+
+    /// Returns `true` if this is the impl block of the given trait, or the
+    /// inherent impl block if `trait_name` is `None`, for the given type.
+    pub(crate) fn is_impl_of(&self, target: &str, trait_name: Option<&str>) -> bool {
+        self.target.name() == Some(target)
+            && self.impl_trait.as_ref().and_then(Type::name) == trait_name
+    }
+
     /// Add a generic to the impl block.
     ///
     /// This adds the generic for the block (`impl<T>`) and not the target type.
     pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.generics.push(name.to_string());
+        for param in GenericParam::parse_list(name) {
+            GenericParam::push(&mut self.generics, param);
+        }
+        self
+    }
+
+    /// Push a generic parameter to the impl block.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        GenericParam::push(&mut self.generics, param);
         self
     }
 
     /// Add a generic to the target type.
     pub fn target_generic<T>(&mut self, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<GenericArg>,
     {
         self.target.generic(ty);
         self
@@ -79,30 +128,91 @@ impl Impl {
         self
     }
 
+    /// Set the impl block documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an attribute to the impl block.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Set whether this impl block is unsafe or not, e.g.
+    /// `unsafe impl Send for Foo`.
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Set whether this impl block is negative or not, e.g.
+    /// `impl !Sync for Foo`.
+    ///
+    /// Negative impls must implement a trait and cannot contain any items.
+    pub fn set_negative(&mut self, negative: bool) -> &mut Self {
+        self.negative = negative;
+        self
+    }
+
     /// Set an associated type.
     pub fn associate_type<T>(&mut self, name: &str, ty: T) -> &mut Self
     where
         T: Into<Type>,
     {
-        self.assoc_tys.push(Field {
-            name: name.to_string(),
-            ty: ty.into(),
-            documentation: Vec::new(),
-            annotation: Vec::new(),
-        });
+        self.new_associated_type(name, ty);
+        self
+    }
 
+    /// Push a new associated type, returning a mutable reference to it.
+    pub fn new_associated_type<T>(&mut self, name: &str, ty: T) -> &mut AssociatedType
+    where
+        T: Into<Type>,
+    {
+        let mut item = AssociatedType::new(name);
+        item.ty(ty);
+
+        self.push_associated_type(item);
+        self.assoc_tys.last_mut().unwrap()
+    }
+
+    /// Push an associated type.
+    pub fn push_associated_type(&mut self, item: AssociatedType) -> &mut Self {
+        self.assoc_tys.push(item);
         self
     }
 
     /// Add a `where` bound to the impl block.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
     pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        let mut bound = Bound::parse_lhs(name);
+        bound.bound(ty);
+        self.push_bound(bound)
+    }
+
+    /// Push a `where` predicate to the impl block.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        Bound::push(&mut self.bounds, bound);
+        self
+    }
+
+    /// Push a new associated constant, returning a mutable reference to it.
+    pub fn new_const<T>(&mut self, name: &str, ty: T) -> &mut Const
     where
         T: Into<Type>,
     {
-        self.bounds.push(Bound {
-            name: name.to_string(),
-            bound: vec![ty.into()],
-        });
+        self.push_const(Const::new(name, ty));
+        self.consts.last_mut().unwrap()
+    }
+
+    /// Push an associated constant.
+    pub fn push_const(&mut self, item: Const) -> &mut Self {
+        self.consts.push(item);
         self
     }
 
@@ -118,16 +228,75 @@ impl Impl {
         self
     }
 
+    /// Call `f` with the type paths used by the impl block, along with the
+    /// names of its generic parameters and those of its functions.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for param in &mut self.generics {
+            param.visit_paths(f);
+        }
+
+        self.target.visit_paths(f);
+
+        if let Some(ref mut ty) = self.impl_trait {
+            ty.visit_paths(f);
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_paths(f);
+        }
+
+        for ty in &mut self.assoc_tys {
+            ty.visit_paths(f);
+        }
+
+        for item in &mut self.consts {
+            item.visit_paths(f);
+        }
+
+        for func in &mut self.fns {
+            func.visit_paths(f);
+        }
+    }
+
     /// Formats the impl block using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
         for m in self.macros.iter() {
-            write!(fmt, "{}\n", m)?;
+            writeln!(fmt, "{}", m)?;
+        }
+
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
         }
+
         write!(fmt, "impl")?;
         fmt_generics(&self.generics[..], fmt)?;
 
+        if self.negative {
+            assert!(
+                self.impl_trait.is_some(),
+                "negative impls must implement a trait"
+            );
+            assert!(
+                self.assoc_tys.is_empty() && self.consts.is_empty() && self.fns.is_empty(),
+                "negative impls cannot contain items"
+            );
+        }
+
         if let Some(ref t) = self.impl_trait {
             write!(fmt, " ")?;
+
+            if self.negative {
+                write!(fmt, "!")?;
+            }
+
             t.fmt(fmt)?;
             write!(fmt, " for")?;
         }
@@ -139,17 +308,17 @@ impl Impl {
 
         fmt.block(|fmt| {
             // format associated types
-            if !self.assoc_tys.is_empty() {
-                for ty in &self.assoc_tys {
-                    write!(fmt, "type {} = ", ty.name)?;
-                    ty.ty.fmt(fmt)?;
-                    write!(fmt, ";\n")?;
-                }
+            for ty in &self.assoc_tys {
+                ty.fmt(false, fmt)?;
+            }
+
+            for c in &self.consts {
+                c.fmt(false, fmt)?;
             }
 
             for (i, func) in self.fns.iter().enumerate() {
-                if i != 0 || !self.assoc_tys.is_empty() {
-                    write!(fmt, "\n")?;
+                if i != 0 || !self.assoc_tys.is_empty() || !self.consts.is_empty() {
+                    writeln!(fmt)?;
                 }
 
                 func.fmt(false, fmt)?;
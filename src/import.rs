@@ -1,7 +1,81 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+
+use indexmap::IndexMap;
+
+use crate::formatter::Formatter;
+
+/// Controls how `use` statements are grouped, matching rustfmt's
+/// `group_imports` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupImports {
+    /// Keep `use` statements in the order the imports were added.
+    Preserve,
+
+    /// Sort `use` statements into groups of `std`, `core` and `alloc`
+    /// imports, external crate imports, and `self`, `super` and `crate`
+    /// imports, separated by blank lines.
+    StdExternalCrate,
+
+    /// Sort all `use` statements into a single group.
+    One,
+}
+
+/// Controls how imports are merged into `use` statements, matching rustfmt's
+/// `imports_granularity` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportsGranularity {
+    /// Merge imports that were added with the same path.
+    Preserve,
+
+    /// Merge imports from the same crate, e.g. `use std::{fmt, io::Read};`.
+    Crate,
+
+    /// Merge imports from the same module, e.g. `use std::io::{Read, Write};`.
+    Module,
+
+    /// Use a separate `use` statement for each imported item.
+    Item,
+
+    /// Merge all imports into a single `use` statement.
+    One,
+}
+
+/// Import settings of a scope, inherited by nested scopes that do not set
+/// their own.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ImportSettings {
+    /// How `use` statements are grouped
+    pub(crate) group: GroupImports,
+
+    /// How imports are merged into `use` statements
+    pub(crate) granularity: ImportsGranularity,
+
+    /// Whether qualified type paths are imported automatically
+    pub(crate) auto_import: bool,
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        ImportSettings {
+            group: GroupImports::Preserve,
+            granularity: ImportsGranularity::Preserve,
+            auto_import: false,
+        }
+    }
+}
+
 /// Defines an import (`use` statement).
 #[derive(Debug, Clone)]
 pub struct Import {
-    line: String,
+    /// Path of the `use` statement the import belongs to
+    path: String,
+
+    /// Imported item, relative to `path`, e.g. `Foo`, `self`, `*` or `b::C`
+    name: String,
+
+    /// Name the item is imported as, e.g. the `Bar` in `Foo as Bar`
+    alias: Option<String>,
 
     /// Function visibility
     pub vis: Option<String>,
@@ -11,14 +85,222 @@ impl Import {
     /// Return a new import.
     pub fn new(path: &str, ty: &str) -> Self {
         Import {
-            line: format!("{}::{}", path, ty),
+            path: path.to_string(),
+            name: ty.to_string(),
+            alias: None,
             vis: None,
         }
     }
 
+    /// Return a new import of `ty` under a different name, e.g.
+    /// `use std::io::Result as IoResult`.
+    pub fn new_as(path: &str, ty: &str, alias: &str) -> Self {
+        Import {
+            alias: Some(alias.to_string()),
+            ..Import::new(path, ty)
+        }
+    }
+
     /// Set the import visibility.
     pub fn vis(&mut self, vis: &str) -> &mut Self {
         self.vis = Some(vis.to_string());
         self
     }
+
+    /// Returns the path of the `use` statement the import belongs to.
+    pub(crate) fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the full path of the imported item, split into segments.
+    pub(crate) fn segments(&self) -> Vec<&str> {
+        self.path
+            .split("::")
+            .chain(self.name.split("::"))
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Returns the name the item is imported as, if it is renamed.
+    pub(crate) fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    /// Returns the key identifying the import within its `use` statement.
+    pub(crate) fn key(&self) -> String {
+        match self.alias {
+            Some(ref alias) => format!("{} as {}", self.name, alias),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Formats the import as an `extern crate` item.
+    pub(crate) fn fmt_extern_crate(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref vis) = self.vis {
+            write!(fmt, "{} ", vis)?;
+        }
+
+        writeln!(fmt, "extern crate {};", self.key())
+    }
+}
+
+/// A tree of imports sharing a common path, formatted as the body of a single
+/// `use` statement, e.g. `{self, b::{C, D as E}}`.
+#[derive(Debug, Default)]
+pub(crate) struct UseTree {
+    entries: IndexMap<String, Option<UseTree>>,
+}
+
+impl UseTree {
+    /// Insert an import into the tree, nesting it by path segment.
+    pub(crate) fn insert(&mut self, import: &Import) {
+        self.insert_path(&import.name, import.alias());
+    }
+
+    /// Insert the item at `path`, relative to the tree, nesting it by path
+    /// segment.
+    pub(crate) fn insert_path(&mut self, path: &str, alias: Option<&str>) {
+        let mut segments: Vec<_> = path.split("::").collect();
+        let name = segments.pop().unwrap();
+
+        let mut node = self;
+
+        for segment in segments {
+            node = node.subtree(segment);
+        }
+
+        // An item that is also the path of other imports is imported as the
+        // `self` of that path, e.g. `io::{self, Write}`
+        match node.entries.get_mut(&format!("{}::", name)) {
+            Some(Some(subtree)) => subtree.insert_leaf("self", alias),
+            _ => node.insert_leaf(name, alias),
+        }
+    }
+
+    /// Returns the subtree of imports below `segment`, creating it if needed.
+    fn subtree(&mut self, segment: &str) -> &mut UseTree {
+        let key = format!("{}::", segment);
+
+        if !self.entries.contains_key(&key) {
+            let mut subtree = UseTree::default();
+
+            // Imports of the segment itself move into the subtree as `self`
+            let leaves: Vec<_> = self
+                .entries
+                .keys()
+                .filter(|leaf| !leaf.ends_with("::") && entry_name(leaf) == segment)
+                .cloned()
+                .collect();
+
+            for leaf in leaves {
+                self.entries.shift_remove(&leaf);
+                subtree
+                    .entries
+                    .insert(leaf.replacen(segment, "self", 1), None);
+            }
+
+            self.entries.insert(key.clone(), Some(subtree));
+        }
+
+        self.entries.get_mut(&key).unwrap().as_mut().unwrap()
+    }
+
+    fn insert_leaf(&mut self, name: &str, alias: Option<&str>) {
+        let leaf = match alias {
+            Some(alias) => format!("{} as {}", name, alias),
+            None => name.to_string(),
+        };
+
+        self.entries.entry(leaf).or_insert(None);
+    }
+
+    /// Returns `true` if the tree contains no imports.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the tree only imports `self`, i.e. the path itself.
+    pub(crate) fn is_self(&self) -> bool {
+        self.entries.len() == 1 && self.entries.contains_key("self")
+    }
+
+    /// Returns the name of the first entry of the tree.
+    pub(crate) fn first_name(&self) -> &str {
+        self.entries.keys().next().map_or("", |key| entry_name(key))
+    }
+
+    /// Sort the tree recursively, in the order used by rustfmt.
+    pub(crate) fn sort(&mut self) {
+        self.entries.sort_by(|a, _, b, _| {
+            cmp_segments(entry_name(a), entry_name(b)).then_with(|| a.cmp(b))
+        });
+
+        for node in self.entries.values_mut().flatten() {
+            node.sort();
+        }
+    }
+
+    /// Formats the tree using the given formatter.
+    pub(crate) fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if self.entries.len() == 1 {
+            let (key, node) = self.entries.iter().next().unwrap();
+
+            // `self` can only be imported from within braces
+            if entry_name(key) != "self" {
+                return Self::fmt_entry(key, node, fmt);
+            }
+        }
+
+        write!(fmt, "{{")?;
+
+        // `self` always comes first, as rustfmt does
+        let (selves, others): (Vec<_>, Vec<_>) = self
+            .entries
+            .iter()
+            .partition(|(key, _)| entry_name(key) == "self");
+
+        for (i, (key, node)) in selves.into_iter().chain(others).enumerate() {
+            if i != 0 {
+                write!(fmt, ", ")?;
+            }
+
+            Self::fmt_entry(key, node, fmt)?;
+        }
+
+        write!(fmt, "}}")
+    }
+
+    fn fmt_entry(key: &str, node: &Option<UseTree>, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", key)?;
+
+        match *node {
+            Some(ref node) => node.fmt(fmt),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the path segment an entry key starts with, e.g. `Foo` for both
+/// `Foo as Bar` and `Foo::`.
+fn entry_name(key: &str) -> &str {
+    key.split([' ', ':']).next().unwrap()
+}
+
+/// Compare two path segments in the order used by rustfmt: `self`, `super`
+/// and `crate` first, followed by snake case, camel case and upper snake case
+/// names, and finally globs.
+pub(crate) fn cmp_segments(a: &str, b: &str) -> Ordering {
+    fn rank(segment: &str) -> u8 {
+        match segment {
+            "self" => 0,
+            "super" => 1,
+            "crate" => 2,
+            "*" => 6,
+            _ if segment.starts_with(|c: char| c.is_lowercase() || c == '_') => 3,
+            _ if segment.chars().all(|c| !c.is_lowercase()) => 5,
+            _ => 4,
+        }
+    }
+
+    rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
 }
@@ -1,8 +1,14 @@
+use crate::extern_block::ExternBlock;
 use crate::function::Function;
+use crate::macro_rules::MacroRules;
 use crate::module::Module;
+use crate::type_alias::TypeAlias;
+use crate::union::Union;
 
+use crate::r#const::Const;
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
+use crate::r#static::Static;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
 
@@ -14,5 +20,31 @@ pub enum Item {
     Trait(Trait),
     Enum(Enum),
     Impl(Impl),
+    TypeAlias(TypeAlias),
+    Const(Const),
+    Static(Static),
+    Union(Union),
+    MacroRules(MacroRules),
+    ExternBlock(ExternBlock),
     Raw(String),
 }
+
+impl Item {
+    /// Call `f` with the type paths used by the item, along with the names of
+    /// its generic parameters. Modules and raw items are not visited.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match *self {
+            Item::Struct(ref mut v) => v.visit_paths(f),
+            Item::Function(ref mut v) => v.visit_paths(f),
+            Item::Trait(ref mut v) => v.visit_paths(f),
+            Item::Enum(ref mut v) => v.visit_paths(f),
+            Item::Impl(ref mut v) => v.visit_paths(f),
+            Item::TypeAlias(ref mut v) => v.visit_paths(f),
+            Item::Const(ref mut v) => v.visit_paths(f),
+            Item::Static(ref mut v) => v.visit_paths(f),
+            Item::Union(ref mut v) => v.visit_paths(f),
+            Item::ExternBlock(ref mut v) => v.visit_paths(f),
+            Item::Module(..) | Item::MacroRules(..) | Item::Raw(..) => {}
+        }
+    }
+}
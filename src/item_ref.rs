@@ -0,0 +1,134 @@
+use crate::extern_block::ExternBlock;
+use crate::function::Function;
+use crate::item::Item;
+use crate::macro_rules::MacroRules;
+use crate::module::Module;
+use crate::type_alias::TypeAlias;
+use crate::union::Union;
+
+use crate::r#const::Const;
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
+use crate::r#static::Static;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+
+/// A reference to an item defined in a scope.
+#[derive(Debug, Clone, Copy)]
+pub enum ItemRef<'a> {
+    /// A module
+    Module(&'a Module),
+    /// A struct
+    Struct(&'a Struct),
+    /// A function
+    Function(&'a Function),
+    /// A trait
+    Trait(&'a Trait),
+    /// An enum
+    Enum(&'a Enum),
+    /// An impl block
+    Impl(&'a Impl),
+    /// A type alias
+    TypeAlias(&'a TypeAlias),
+    /// A constant
+    Const(&'a Const),
+    /// A static
+    Static(&'a Static),
+    /// A union
+    Union(&'a Union),
+    /// A `macro_rules!` macro
+    MacroRules(&'a MacroRules),
+    /// An `extern` block
+    ExternBlock(&'a ExternBlock),
+    /// A raw string
+    Raw(&'a str),
+}
+
+/// A mutable reference to an item defined in a scope.
+#[derive(Debug)]
+pub enum ItemMut<'a> {
+    /// A module
+    Module(&'a mut Module),
+    /// A struct
+    Struct(&'a mut Struct),
+    /// A function
+    Function(&'a mut Function),
+    /// A trait
+    Trait(&'a mut Trait),
+    /// An enum
+    Enum(&'a mut Enum),
+    /// An impl block
+    Impl(&'a mut Impl),
+    /// A type alias
+    TypeAlias(&'a mut TypeAlias),
+    /// A constant
+    Const(&'a mut Const),
+    /// A static
+    Static(&'a mut Static),
+    /// A union
+    Union(&'a mut Union),
+    /// A `macro_rules!` macro
+    MacroRules(&'a mut MacroRules),
+    /// An `extern` block
+    ExternBlock(&'a mut ExternBlock),
+    /// A raw string
+    Raw(&'a mut String),
+}
+
+impl<'a> ItemRef<'a> {
+    pub(crate) fn new(item: &'a Item) -> Self {
+        match *item {
+            Item::Module(ref v) => ItemRef::Module(v),
+            Item::Struct(ref v) => ItemRef::Struct(v),
+            Item::Function(ref v) => ItemRef::Function(v),
+            Item::Trait(ref v) => ItemRef::Trait(v),
+            Item::Enum(ref v) => ItemRef::Enum(v),
+            Item::Impl(ref v) => ItemRef::Impl(v),
+            Item::TypeAlias(ref v) => ItemRef::TypeAlias(v),
+            Item::Const(ref v) => ItemRef::Const(v),
+            Item::Static(ref v) => ItemRef::Static(v),
+            Item::Union(ref v) => ItemRef::Union(v),
+            Item::MacroRules(ref v) => ItemRef::MacroRules(v),
+            Item::ExternBlock(ref v) => ItemRef::ExternBlock(v),
+            Item::Raw(ref v) => ItemRef::Raw(v),
+        }
+    }
+
+    /// Returns the name of the item, or `None` for impl blocks, `extern`
+    /// blocks and raw strings.
+    pub fn name(&self) -> Option<&'a str> {
+        match *self {
+            ItemRef::Module(v) => Some(&v.name),
+            ItemRef::Struct(v) => Some(v.name()),
+            ItemRef::Function(v) => Some(v.name()),
+            ItemRef::Trait(v) => Some(v.name()),
+            ItemRef::Enum(v) => Some(v.name()),
+            ItemRef::TypeAlias(v) => Some(v.name()),
+            ItemRef::Const(v) => Some(v.name()),
+            ItemRef::Static(v) => Some(v.name()),
+            ItemRef::Union(v) => Some(v.name()),
+            ItemRef::MacroRules(v) => Some(v.name()),
+            ItemRef::Impl(..) | ItemRef::ExternBlock(..) | ItemRef::Raw(..) => None,
+        }
+    }
+}
+
+impl<'a> ItemMut<'a> {
+    pub(crate) fn new(item: &'a mut Item) -> Self {
+        match *item {
+            Item::Module(ref mut v) => ItemMut::Module(v),
+            Item::Struct(ref mut v) => ItemMut::Struct(v),
+            Item::Function(ref mut v) => ItemMut::Function(v),
+            Item::Trait(ref mut v) => ItemMut::Trait(v),
+            Item::Enum(ref mut v) => ItemMut::Enum(v),
+            Item::Impl(ref mut v) => ItemMut::Impl(v),
+            Item::TypeAlias(ref mut v) => ItemMut::TypeAlias(v),
+            Item::Const(ref mut v) => ItemMut::Const(v),
+            Item::Static(ref mut v) => ItemMut::Static(v),
+            Item::Union(ref mut v) => ItemMut::Union(v),
+            Item::MacroRules(ref mut v) => ItemMut::MacroRules(v),
+            Item::ExternBlock(ref mut v) => ItemMut::ExternBlock(v),
+            Item::Raw(ref mut v) => ItemMut::Raw(v),
+        }
+    }
+}
@@ -25,41 +25,65 @@
 //! println!("{}", scope.to_string());
 //! ```
 
+mod arg;
 mod associated_type;
 mod block;
 mod body;
 mod bound;
 mod docs;
+mod extern_block;
 mod field;
 mod fields;
 mod formatter;
 mod function;
+mod generic_param;
 mod import;
 mod item;
+mod item_ref;
+mod macro_rules;
 mod module;
+mod parse;
+mod receiver;
 mod scope;
+mod type_alias;
+mod type_bound;
 mod type_def;
+mod union;
 mod variant;
 
+mod r#const;
 mod r#enum;
 mod r#impl;
+mod r#static;
 mod r#struct;
 mod r#trait;
 mod r#type;
 
-
+pub use arg::*;
 pub use associated_type::*;
 pub use block::*;
+pub use bound::*;
+pub use extern_block::*;
 pub use field::*;
 pub use formatter::*;
 pub use function::*;
+pub use generic_param::*;
 pub use import::*;
+pub use item_ref::*;
+pub use macro_rules::*;
 pub use module::*;
+pub use parse::ParseError;
+pub use receiver::*;
 pub use scope::*;
+pub use type_alias::*;
+pub use type_bound::*;
+pub use union::*;
 pub use variant::*;
 
+pub use r#const::*;
 pub use r#enum::*;
 pub use r#impl::*;
+pub use r#static::*;
 pub use r#struct::*;
 pub use r#trait::*;
 pub use r#type::*;
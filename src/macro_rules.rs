@@ -0,0 +1,185 @@
+use std::fmt::{self, Write};
+
+use crate::block::Block;
+use crate::body::Body;
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+
+/// Defines a `macro_rules!` macro.
+#[derive(Debug, Clone)]
+pub struct MacroRules {
+    /// Name of the macro
+    name: String,
+
+    /// Macro documentation
+    docs: Option<Docs>,
+
+    /// Whether or not the macro is `#[macro_export]`ed
+    export: bool,
+
+    /// Macro attributes, e.g., `#[cfg(test)]`.
+    attributes: Vec<String>,
+
+    /// Macro rules
+    rules: Vec<MacroRule>,
+}
+
+/// Defines a single rule of a `macro_rules!` macro.
+#[derive(Debug, Clone)]
+pub struct MacroRule {
+    /// The pattern matched by the rule, e.g. `($e:expr)`
+    matcher: String,
+
+    /// The expansion of the rule
+    body: Vec<Body>,
+}
+
+impl MacroRules {
+    /// Return a new macro definition.
+    pub fn new(name: &str) -> Self {
+        MacroRules {
+            name: name.to_string(),
+            docs: None,
+            export: false,
+            attributes: vec![],
+            rules: vec![],
+        }
+    }
+
+    /// Returns the macro name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set the macro documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Set whether this macro is exported with `#[macro_export]` or not.
+    pub fn set_export(&mut self, export: bool) -> &mut Self {
+        self.export = export;
+        self
+    }
+
+    /// Add an attribute to the macro.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Push a new rule, returning a mutable reference to it.
+    pub fn new_rule(&mut self, matcher: &str) -> &mut MacroRule {
+        self.push_rule(MacroRule::new(matcher));
+        self.rules.last_mut().unwrap()
+    }
+
+    /// Push a rule.
+    pub fn push_rule(&mut self, rule: MacroRule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Formats the macro using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        if self.export {
+            writeln!(fmt, "#[macro_export]")?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        write!(fmt, "macro_rules! {}", self.name)?;
+
+        fmt.block(|fmt| {
+            for rule in &self.rules {
+                rule.fmt(fmt)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl MacroRule {
+    /// Return a new rule with the given matcher, e.g. `($e:expr)`.
+    ///
+    /// The matcher is wrapped in parentheses if it is not already delimited.
+    pub fn new(matcher: &str) -> Self {
+        let matcher = matcher.trim();
+
+        let matcher = if is_delimited(matcher) {
+            matcher.to_string()
+        } else {
+            format!("({})", matcher)
+        };
+
+        MacroRule {
+            matcher,
+            body: vec![],
+        }
+    }
+
+    /// Push a line to the rule expansion.
+    pub fn line<T>(&mut self, line: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.body.push(Body::String(line.to_string()));
+        self
+    }
+
+    /// Push a block to the rule expansion.
+    pub fn push_block(&mut self, block: Block) -> &mut Self {
+        self.body.push(Body::Block(block));
+        self
+    }
+
+    /// Formats the rule using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(fmt, "{} => {{", self.matcher)?;
+
+        fmt.indent(|fmt| {
+            for b in &self.body {
+                b.fmt(fmt)?;
+            }
+
+            Ok(())
+        })?;
+
+        writeln!(fmt, "}};")
+    }
+}
+
+/// Returns `true` if the whole matcher is enclosed in a single pair of
+/// delimiters, e.g. `($a:expr)` but not `($a:expr) + ($b:expr)`.
+fn is_delimited(matcher: &str) -> bool {
+    if !matcher.starts_with(['(', '[', '{']) {
+        return false;
+    }
+
+    let mut depth = 0;
+
+    for (i, c) in matcher.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+
+                // The opening delimiter is closed, which must be at the end
+                if depth == 0 {
+                    return i == matcher.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
@@ -1,14 +1,22 @@
 use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
 
-use crate::docs::Docs;
+use crate::extern_block::ExternBlock;
 use crate::formatter::Formatter;
 use crate::function::Function;
+use crate::import::ImportSettings;
+use crate::macro_rules::MacroRules;
 use crate::scope::Scope;
+use crate::type_alias::TypeAlias;
+use crate::union::Union;
 
+use crate::r#const::Const;
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
+use crate::r#static::Static;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
+use crate::r#type::Type;
 
 /// Defines a module.
 #[derive(Debug, Clone)]
@@ -19,11 +27,20 @@ pub struct Module {
     /// Visibility
     vis: Option<String>,
 
-    /// Module documentation
-    docs: Option<Docs>,
+    /// Whether or not the module is `#[macro_use]`
+    macro_use: bool,
+
+    /// Module attributes, e.g., `#[cfg(test)]`.
+    attributes: Vec<String>,
+
+    /// Whether the module contents are written inline or in a separate file
+    inline: bool,
+
+    /// Whether the module file is `name/mod.rs` rather than `name.rs`
+    mod_rs: bool,
 
     /// Contents of the module
-    scope: Scope,
+    pub(crate) scope: Scope,
 }
 
 impl Module {
@@ -32,7 +49,10 @@ impl Module {
         Module {
             name: name.to_string(),
             vis: None,
-            docs: None,
+            macro_use: false,
+            attributes: vec![],
+            inline: true,
+            mod_rs: false,
             scope: Scope::new(),
         }
     }
@@ -48,6 +68,49 @@ impl Module {
         self
     }
 
+    /// Set whether the module is annotated with `#[macro_use]` or not, making
+    /// the macros it defines available after the module declaration.
+    pub fn set_macro_use(&mut self, macro_use: bool) -> &mut Self {
+        self.macro_use = macro_use;
+        self
+    }
+
+    /// Set the module documentation, emitted as inner `//!` doc comments at
+    /// the top of the module contents.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.scope.doc(docs);
+        self
+    }
+
+    /// Add an attribute to the module declaration, e.g. `#[cfg(test)]`.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Add an inner attribute to the module contents, e.g.
+    /// `#![allow(dead_code)]`.
+    pub fn inner_attr(&mut self, attribute: &str) -> &mut Self {
+        self.scope.inner_attr(attribute);
+        self
+    }
+
+    /// Set whether the module contents are written inline or not.
+    ///
+    /// A module that is not inline is declared as `mod name;`, and its
+    /// contents must be rendered separately from [`Module::scope`].
+    pub fn set_inline(&mut self, inline: bool) -> &mut Self {
+        self.inline = inline;
+        self
+    }
+
+    /// Set whether the contents of a module that is not inline are written to
+    /// `name/mod.rs` rather than `name.rs`.
+    pub fn set_mod_rs(&mut self, mod_rs: bool) -> &mut Self {
+        self.mod_rs = mod_rs;
+        self
+    }
+
     /// Import a type into the module's scope.
     ///
     /// This results in a new `use` statement bein added to the beginning of the
@@ -57,6 +120,25 @@ impl Module {
         self
     }
 
+    /// Import an item into the module's scope under a different name.
+    pub fn import_as(&mut self, path: &str, ty: &str, alias: &str) -> &mut Self {
+        self.scope.import_as(path, ty, alias);
+        self
+    }
+
+    /// Import all items of a `use` tree into the module's scope.
+    pub fn import_tree(&mut self, tree: &str) -> &mut Self {
+        self.scope.import_tree(tree);
+        self
+    }
+
+    /// Import all items of a `use` tree into the module's scope with the given
+    /// visibility.
+    pub fn reexport_tree(&mut self, vis: &str, tree: &str) -> &mut Self {
+        self.scope.reexport_tree(vis, tree);
+        self
+    }
+
     /// Push a new module definition, returning a mutable reference to it.
     ///
     /// # Panics
@@ -123,6 +205,17 @@ impl Module {
         self
     }
 
+    /// Push a new union definition, returning a mutable reference to it.
+    pub fn new_union(&mut self, name: &str) -> &mut Union {
+        self.scope.new_union(name)
+    }
+
+    /// Push a union definition
+    pub fn push_union(&mut self, item: Union) -> &mut Self {
+        self.scope.push_union(item);
+        self
+    }
+
     /// Push a new function definition, returning a mutable reference to it.
     pub fn new_fn(&mut self, name: &str) -> &mut Function {
         self.scope.new_fn(name)
@@ -162,13 +255,122 @@ impl Module {
         self
     }
 
+    /// Push a new type alias definition, returning a mutable reference to it.
+    pub fn new_type_alias<T>(&mut self, name: &str, target: T) -> &mut TypeAlias
+    where
+        T: Into<Type>,
+    {
+        self.scope.new_type_alias(name, target)
+    }
+
+    /// Push a type alias definition.
+    pub fn push_type_alias(&mut self, item: TypeAlias) -> &mut Self {
+        self.scope.push_type_alias(item);
+        self
+    }
+
+    /// Push a new `const` definition, returning a mutable reference to it.
+    pub fn new_const<T>(&mut self, name: &str, ty: T) -> &mut Const
+    where
+        T: Into<Type>,
+    {
+        self.scope.new_const(name, ty)
+    }
+
+    /// Push a `const` definition.
+    pub fn push_const(&mut self, item: Const) -> &mut Self {
+        self.scope.push_const(item);
+        self
+    }
+
+    /// Push a new `static` definition, returning a mutable reference to it.
+    pub fn new_static<T>(&mut self, name: &str, ty: T) -> &mut Static
+    where
+        T: Into<Type>,
+    {
+        self.scope.new_static(name, ty)
+    }
+
+    /// Push a `static` definition.
+    pub fn push_static(&mut self, item: Static) -> &mut Self {
+        self.scope.push_static(item);
+        self
+    }
+
+    /// Push a new `macro_rules!` definition, returning a mutable reference to
+    /// it.
+    pub fn new_macro_rules(&mut self, name: &str) -> &mut MacroRules {
+        self.scope.new_macro_rules(name)
+    }
+
+    /// Push a `macro_rules!` definition.
+    pub fn push_macro_rules(&mut self, item: MacroRules) -> &mut Self {
+        self.scope.push_macro_rules(item);
+        self
+    }
+
+    /// Push a new `extern` block using the given ABI, returning a mutable
+    /// reference to it.
+    pub fn new_extern_block(&mut self, abi: &str) -> &mut ExternBlock {
+        self.scope.new_extern_block(abi)
+    }
+
+    /// Push an `extern` block.
+    pub fn push_extern_block(&mut self, item: ExternBlock) -> &mut Self {
+        self.scope.push_extern_block(item);
+        self
+    }
+
+    /// Push the files backing this module and its submodules, where `dir` is
+    /// the directory corresponding to the module's path.
+    pub(crate) fn push_files(
+        &self,
+        dir: &Path,
+        settings: ImportSettings,
+        files: &mut Vec<(PathBuf, String)>,
+    ) {
+        if !self.inline {
+            let file = if self.mod_rs {
+                dir.join("mod.rs")
+            } else {
+                dir.with_file_name(format!("{}.rs", self.name))
+            };
+
+            files.push((file, self.scope.to_file_string(settings)));
+        }
+
+        self.scope.push_module_files(dir, settings, files);
+    }
+
     /// Formats the module using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with(ImportSettings::default(), fmt)
+    }
+
+    /// Formats the module, inheriting the given import settings.
+    pub(crate) fn fmt_with(
+        &self,
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        if self.macro_use {
+            writeln!(fmt, "#[macro_use]")?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
         if let Some(ref vis) = self.vis {
             write!(fmt, "{} ", vis)?;
         }
 
         write!(fmt, "mod {}", self.name)?;
-        fmt.block(|fmt| self.scope.fmt(fmt))
+
+        if self.inline {
+            fmt.block(|fmt| self.scope.fmt_with(settings, fmt))
+        } else {
+            writeln!(fmt, ";")
+        }
     }
 }
@@ -0,0 +1,659 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::bound::Bound;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
+
+use crate::r#type::{GenericArg, Type};
+
+/// Error returned when a string cannot be parsed into a `Type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    src: String,
+    pos: usize,
+    msg: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "failed to parse `{}` at offset {}: {}",
+            self.src, self.pos, self.msg
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A small recursive descent parser for the subset of Rust syntax that can be
+/// passed to the builders as strings.
+pub(crate) struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Parse `src` with `f`, requiring that the entire input is consumed.
+    pub(crate) fn parse_all<T, F>(src: &'a str, f: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, ParseError>,
+    {
+        let mut parser = Parser { src, pos: 0 };
+        let ret = f(&mut parser)?;
+
+        if !parser.is_done() {
+            return Err(parser.error("unexpected trailing input"));
+        }
+
+        Ok(ret)
+    }
+
+    pub(crate) fn error(&self, msg: &str) -> ParseError {
+        ParseError {
+            src: self.src.to_string(),
+            pos: self.pos,
+            msg: msg.to_string(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    pub(crate) fn is_done(&mut self) -> bool {
+        self.skip_ws();
+        self.rest().is_empty()
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<char> {
+        self.skip_ws();
+        self.rest().chars().next()
+    }
+
+    /// Consume `token` if the input starts with it.
+    pub(crate) fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the keyword `kw` if the next identifier is exactly `kw`.
+    pub(crate) fn eat_keyword(&mut self, kw: &str) -> bool {
+        let pos = self.pos;
+
+        match self.ident() {
+            Some(ident) if ident == kw => true,
+            _ => {
+                self.pos = pos;
+                false
+            }
+        }
+    }
+
+    pub(crate) fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", token)))
+        }
+    }
+
+    pub(crate) fn ident(&mut self) -> Option<&'a str> {
+        self.skip_ws();
+
+        let rest = self.rest();
+        let start = if rest.starts_with("r#") { 2 } else { 0 };
+
+        let first = rest[start..].chars().next()?;
+
+        if !(first.is_alphabetic() || first == '_') {
+            return None;
+        }
+
+        let len = rest[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - start);
+
+        self.pos += start + len;
+        Some(&rest[..start + len])
+    }
+
+    pub(crate) fn lifetime(&mut self) -> Option<&'a str> {
+        self.skip_ws();
+
+        let pos = self.pos;
+
+        if !self.eat("'") {
+            return None;
+        }
+
+        // Lifetimes are directly followed by their name
+        let has_name = self
+            .rest()
+            .starts_with(|c: char| c.is_alphabetic() || c == '_');
+
+        if !has_name || self.ident().is_none() {
+            self.pos = pos;
+            return None;
+        }
+
+        Some(&self.src[pos..self.pos])
+    }
+
+    /// Parse a raw expression, stopping at a top-level `,`, `;`, `>` or a
+    /// closing delimiter.
+    pub(crate) fn expr(&mut self) -> Result<&'a str, ParseError> {
+        self.skip_ws();
+
+        let rest = self.rest();
+        let mut depth = 0usize;
+        let mut end = rest.len();
+
+        for (i, c) in rest.char_indices() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                ')' | ']' | '}' => depth -= 1,
+                ',' | ';' | '>' if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let expr = rest[..end].trim_end();
+
+        if expr.is_empty() {
+            return Err(self.error("expected expression"));
+        }
+
+        self.pos += end;
+        Ok(expr)
+    }
+
+    /// Parse a type.
+    pub(crate) fn ty(&mut self) -> Result<Type, ParseError> {
+        self.ty_inner(true)
+    }
+
+    fn ty_inner(&mut self, allow_plus: bool) -> Result<Type, ParseError> {
+        if self.eat("!") {
+            Ok(Type::never())
+        } else if self.eat("&") {
+            let lifetime = self.lifetime();
+            let mutable = self.eat_keyword("mut");
+
+            let inner = self.ty_inner(false)?;
+            let mut ty = if mutable {
+                Type::mut_reference(inner)
+            } else {
+                Type::reference(inner)
+            };
+
+            if let Some(lifetime) = lifetime {
+                ty.lifetime(lifetime);
+            }
+
+            Ok(ty)
+        } else if self.eat("*") {
+            if self.eat_keyword("const") {
+                Ok(Type::ptr(self.ty_inner(false)?))
+            } else if self.eat_keyword("mut") {
+                Ok(Type::mut_ptr(self.ty_inner(false)?))
+            } else {
+                Err(self.error("expected `const` or `mut`"))
+            }
+        } else if self.eat("[") {
+            let elem = self.ty()?;
+
+            let ty = if self.eat(";") {
+                Type::array(elem, self.expr()?)
+            } else {
+                Type::slice(elem)
+            };
+
+            self.expect("]")?;
+            Ok(ty)
+        } else if self.eat("(") {
+            let mut tys = vec![];
+            let mut trailing = false;
+
+            while !self.eat(")") {
+                tys.push(self.ty()?);
+                trailing = self.eat(",");
+
+                if !trailing {
+                    self.expect(")")?;
+                    break;
+                }
+            }
+
+            if tys.len() == 1 && !trailing {
+                // Parenthesized type
+                Ok(tys.pop().unwrap())
+            } else {
+                Ok(Type::tuple(tys))
+            }
+        } else if let Some(ty) = self.fn_ptr()? {
+            Ok(ty)
+        } else if self.eat_keyword("dyn") {
+            Ok(Type::dyn_trait(self.bounds_inner(allow_plus)?))
+        } else if self.eat_keyword("impl") {
+            Ok(Type::impl_trait(self.bounds_inner(allow_plus)?))
+        } else {
+            self.path()
+        }
+    }
+
+    /// Parse a function pointer type, e.g. `for<'a> unsafe extern "C" fn(&'a u8)`,
+    /// if the input starts with one.
+    fn fn_ptr(&mut self) -> Result<Option<Type>, ParseError> {
+        let pos = self.pos;
+
+        let lifetimes = if self.eat_keyword("for") {
+            self.lifetimes()?
+        } else {
+            vec![]
+        };
+
+        let r#unsafe = self.eat_keyword("unsafe");
+
+        let abi = if self.eat_keyword("extern") {
+            Some(self.abi()?)
+        } else {
+            None
+        };
+
+        if !self.eat_keyword("fn") {
+            if self.pos == pos {
+                return Ok(None);
+            }
+
+            return Err(self.error("expected `fn`"));
+        }
+
+        let mut ty = Type::fn_ptr(self.ty_list()?);
+
+        for lifetime in lifetimes {
+            ty.for_lifetime(lifetime);
+        }
+
+        ty.set_unsafe(r#unsafe);
+
+        if let Some(abi) = abi {
+            ty.extern_abi(abi);
+        }
+
+        if self.eat("->") {
+            ty.ret(self.ty_inner(false)?);
+        }
+
+        Ok(Some(ty))
+    }
+
+    /// Parse the optional ABI string of an `extern` qualifier, e.g. `"C"`,
+    /// returning an empty string if there is none.
+    fn abi(&mut self) -> Result<&'a str, ParseError> {
+        if !self.eat("\"") {
+            return Ok("");
+        }
+
+        let rest = self.rest();
+
+        match rest.find('"') {
+            Some(end) => {
+                self.pos += end + 1;
+                Ok(&rest[..end])
+            }
+            None => Err(self.error("unterminated ABI string")),
+        }
+    }
+
+    /// Parse a parenthesized, comma separated list of types.
+    fn ty_list(&mut self) -> Result<Vec<Type>, ParseError> {
+        let mut tys = vec![];
+
+        self.expect("(")?;
+
+        while !self.eat(")") {
+            tys.push(self.ty()?);
+
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+
+        Ok(tys)
+    }
+
+    fn path(&mut self) -> Result<Type, ParseError> {
+        let mut name = String::new();
+        let mut turbofish = false;
+
+        if self.peek() == Some('<') {
+            // A qualified self type, e.g. `<T as Iterator>::Item`, is kept
+            // verbatim as the start of the path
+            let start = self.pos;
+
+            self.expect("<")?;
+            self.ty()?;
+
+            if self.eat_keyword("as") {
+                self.path()?;
+            }
+
+            self.expect(">")?;
+            name.push_str(&self.src[start..self.pos]);
+
+            self.expect("::")?;
+            name.push_str("::");
+        } else if self.eat("::") {
+            name.push_str("::");
+        }
+
+        loop {
+            match self.ident() {
+                Some(ident) => name.push_str(ident),
+                None => return Err(self.error("expected type")),
+            }
+
+            if self.rest().trim_start().starts_with("::<") {
+                self.expect("::")?;
+                turbofish = true;
+                break;
+            }
+
+            if !self.eat("::") {
+                break;
+            }
+
+            name.push_str("::");
+        }
+
+        if name == "_" {
+            return Ok(Type::infer());
+        }
+
+        let mut ty = Type::new_path(name);
+        ty.set_turbofish(turbofish);
+
+        if self.eat("<") {
+            while !self.eat(">") {
+                ty.generic(self.generic_arg()?);
+
+                if !self.eat(",") {
+                    self.expect(">")?;
+                    break;
+                }
+            }
+        } else if self.peek() == Some('(') {
+            let args = self.ty_list()?;
+            let ret = if self.eat("->") {
+                Some(self.ty_inner(false)?)
+            } else {
+                None
+            };
+
+            ty = Type::parenthesized(ty.name().unwrap(), args, ret);
+        }
+
+        if self.rest().trim_start().starts_with("::") {
+            return Err(self.error("associated items of generic types are not supported"));
+        }
+
+        Ok(ty)
+    }
+
+    /// Parse a single generic argument.
+    pub(crate) fn generic_arg(&mut self) -> Result<GenericArg, ParseError> {
+        if let Some(lifetime) = self.lifetime() {
+            return Ok(GenericArg::lifetime(lifetime));
+        }
+
+        // Check for a const argument, e.g. `4`, `true` or `{ N * 2 }`
+        match self.peek() {
+            Some(c) if c == '{' || c == '-' || c == '"' || c.is_ascii_digit() => {
+                return Ok(GenericArg::constant(self.expr()?));
+            }
+            _ => {}
+        }
+
+        if self.eat_keyword("true") {
+            return Ok(GenericArg::constant("true"));
+        } else if self.eat_keyword("false") {
+            return Ok(GenericArg::constant("false"));
+        }
+
+        // Check for an associated type binding, e.g. `Item = T`
+        let pos = self.pos;
+
+        if let Some(name) = self.ident() {
+            if self.eat("=") {
+                return Ok(GenericArg::binding(name, self.ty()?));
+            }
+        }
+
+        self.pos = pos;
+
+        Ok(GenericArg::from(self.ty()?))
+    }
+
+    /// Parse a `+` separated list of bounds.
+    pub(crate) fn bounds(&mut self) -> Result<Vec<TypeBound>, ParseError> {
+        self.bounds_inner(true)
+    }
+
+    fn bounds_inner(&mut self, allow_plus: bool) -> Result<Vec<TypeBound>, ParseError> {
+        let mut bounds = vec![self.bound()?];
+
+        while allow_plus && self.eat("+") {
+            bounds.push(self.bound()?);
+        }
+
+        Ok(bounds)
+    }
+
+    /// Parse the lifetimes of a `for<...>` binder, after the `for` keyword.
+    fn lifetimes(&mut self) -> Result<Vec<&'a str>, ParseError> {
+        let mut lifetimes = vec![];
+
+        self.expect("<")?;
+
+        while !self.eat(">") {
+            match self.lifetime() {
+                Some(lifetime) => lifetimes.push(lifetime),
+                None => return Err(self.error("expected lifetime")),
+            }
+
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+
+        Ok(lifetimes)
+    }
+
+    /// Parse a single bound.
+    pub(crate) fn bound(&mut self) -> Result<TypeBound, ParseError> {
+        if let Some(lifetime) = self.lifetime() {
+            return Ok(TypeBound::lifetime(lifetime));
+        }
+
+        if self.eat("(") {
+            let bound = self.bound()?;
+            self.expect(")")?;
+            return Ok(bound);
+        }
+
+        let maybe = self.eat("?");
+        let lifetimes = if self.eat_keyword("for") {
+            self.lifetimes()?
+        } else {
+            vec![]
+        };
+
+        let ty = self.path()?;
+        let mut bound = if maybe {
+            TypeBound::maybe(ty)
+        } else {
+            TypeBound::new(ty)
+        };
+
+        for lifetime in lifetimes {
+            bound.for_lifetime(lifetime);
+        }
+
+        Ok(bound)
+    }
+
+    /// Parse a comma separated list of generic parameters.
+    pub(crate) fn generic_params(&mut self) -> Result<Vec<GenericParam>, ParseError> {
+        let mut params = vec![self.generic_param()?];
+
+        while self.eat(",") && !self.is_done() {
+            params.push(self.generic_param()?);
+        }
+
+        Ok(params)
+    }
+
+    /// Parse a single generic parameter.
+    pub(crate) fn generic_param(&mut self) -> Result<GenericParam, ParseError> {
+        let mut param = if let Some(lifetime) = self.lifetime() {
+            GenericParam::lifetime(lifetime)
+        } else if self.eat_keyword("const") {
+            let name = match self.ident() {
+                Some(name) => name,
+                None => return Err(self.error("expected const parameter name")),
+            };
+
+            self.expect(":")?;
+            let mut param = GenericParam::constant(name, self.ty()?);
+
+            if self.eat("=") {
+                param.default(self.generic_arg()?);
+            }
+
+            return Ok(param);
+        } else if let Some(name) = self.ident() {
+            GenericParam::new(name)
+        } else {
+            return Err(self.error("expected generic parameter"));
+        };
+
+        if self.eat(":") {
+            for bound in self.bounds()? {
+                param.bound(bound);
+            }
+        }
+
+        if self.eat("=") {
+            param.default(self.generic_arg()?);
+        }
+
+        Ok(param)
+    }
+
+    /// Parse the left-hand side of a `where` predicate, e.g. `for<'a> F`.
+    pub(crate) fn where_predicate_lhs(&mut self) -> Result<Bound, ParseError> {
+        let lifetimes = if self.eat_keyword("for") {
+            self.lifetimes()?
+        } else {
+            vec![]
+        };
+
+        let mut bound = match self.lifetime() {
+            Some(lifetime) => Bound::lifetime(lifetime),
+            None => Bound::new(self.ty()?),
+        };
+
+        for lifetime in lifetimes {
+            bound.for_lifetime(lifetime);
+        }
+
+        Ok(bound)
+    }
+
+    /// Parse a `where` predicate, e.g. `for<'a> F: Fn(&'a str)`.
+    pub(crate) fn where_predicate(&mut self) -> Result<Bound, ParseError> {
+        let mut bound = self.where_predicate_lhs()?;
+
+        self.expect(":")?;
+
+        for b in self.bounds()? {
+            bound.bound(b);
+        }
+
+        Ok(bound)
+    }
+
+    /// Parse a `use` tree, e.g. `a::{self, b::{C, D as E}}` or `a::*`,
+    /// pushing the full path and rename of each imported item to `out`.
+    pub(crate) fn use_tree(
+        &mut self,
+        prefix: &[&'a str],
+        out: &mut Vec<(Vec<&'a str>, Option<&'a str>)>,
+    ) -> Result<(), ParseError> {
+        let mut path = prefix.to_vec();
+
+        loop {
+            if self.eat("*") {
+                path.push("*");
+                out.push((path, None));
+                return Ok(());
+            }
+
+            if self.eat("{") {
+                while !self.eat("}") {
+                    self.use_tree(&path, out)?;
+
+                    if !self.eat(",") {
+                        self.expect("}")?;
+                        break;
+                    }
+                }
+
+                return Ok(());
+            }
+
+            match self.ident() {
+                Some(ident) => path.push(ident),
+                None => return Err(self.error("expected an identifier")),
+            }
+
+            if !self.eat("::") {
+                break;
+            }
+        }
+
+        let alias = if self.eat_keyword("as") {
+            match self.ident() {
+                Some(ident) => Some(ident),
+                None => return Err(self.error("expected an identifier")),
+            }
+        } else {
+            None
+        };
+
+        out.push((path, alias));
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+
+use crate::r#type::Type;
+
+/// Defines the `self` receiver of a method, e.g. `&'a mut self` or
+/// `self: Pin<&mut Self>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receiver {
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    /// `self` or `mut self`
+    Value { mutable: bool },
+
+    /// `&self`, `&'a mut self`, ...
+    Reference {
+        lifetime: Option<String>,
+        mutable: bool,
+    },
+
+    /// `self: Box<Self>`, `mut self: Rc<Self>`, ...
+    Typed { mutable: bool, ty: Type },
+}
+
+impl Receiver {
+    /// Return a receiver taking `self` by value.
+    pub fn value() -> Self {
+        Receiver {
+            kind: Kind::Value { mutable: false },
+        }
+    }
+
+    /// Return a receiver taking `self` by value with a mutable binding, i.e.
+    /// `mut self`.
+    pub fn mut_value() -> Self {
+        Receiver {
+            kind: Kind::Value { mutable: true },
+        }
+    }
+
+    /// Return a receiver taking `&self`.
+    pub fn reference() -> Self {
+        Receiver {
+            kind: Kind::Reference {
+                lifetime: None,
+                mutable: false,
+            },
+        }
+    }
+
+    /// Return a receiver taking `&mut self`.
+    pub fn mut_reference() -> Self {
+        Receiver {
+            kind: Kind::Reference {
+                lifetime: None,
+                mutable: true,
+            },
+        }
+    }
+
+    /// Return a receiver with an explicit type, e.g. `self: Box<Self>`.
+    pub fn typed<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Receiver {
+            kind: Kind::Typed {
+                mutable: false,
+                ty: ty.into(),
+            },
+        }
+    }
+
+    /// Set the lifetime of a reference receiver, e.g. `&'a self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver is not a reference.
+    pub fn lifetime(&mut self, lifetime: &str) -> &mut Self {
+        match self.kind {
+            Kind::Reference {
+                lifetime: ref mut lt,
+                ..
+            } => *lt = Some(lifetime.to_string()),
+            _ => panic!("only reference receivers have a lifetime"),
+        }
+
+        self
+    }
+
+    /// Set whether the `self` binding of a by-value or typed receiver is
+    /// mutable or not, e.g. `mut self: Box<Self>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver is a reference.
+    pub fn set_mut(&mut self, r#mut: bool) -> &mut Self {
+        match self.kind {
+            Kind::Value { ref mut mutable }
+            | Kind::Typed {
+                ref mut mutable, ..
+            } => *mutable = r#mut,
+            Kind::Reference { .. } => {
+                panic!("use `Receiver::mut_reference` for `&mut self` receivers")
+            }
+        }
+
+        self
+    }
+
+    /// Call `f` with the type paths used by a typed receiver.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        if let Kind::Typed { ref mut ty, .. } = self.kind {
+            ty.visit_paths(f);
+        }
+    }
+
+    /// Formats the receiver using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::Value { mutable } => {
+                if mutable {
+                    write!(fmt, "mut ")?;
+                }
+
+                write!(fmt, "self")
+            }
+            Kind::Reference {
+                ref lifetime,
+                mutable,
+            } => {
+                write!(fmt, "&")?;
+
+                if let Some(ref lifetime) = *lifetime {
+                    write!(fmt, "{} ", lifetime)?;
+                }
+
+                if mutable {
+                    write!(fmt, "mut ")?;
+                }
+
+                write!(fmt, "self")
+            }
+            Kind::Typed { mutable, ref ty } => {
+                if mutable {
+                    write!(fmt, "mut ")?;
+                }
+
+                write!(fmt, "self: ")?;
+                ty.fmt(fmt)
+            }
+        }
+    }
+}
@@ -1,18 +1,35 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
 
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 
 use crate::docs::Docs;
+use crate::extern_block::ExternBlock;
 use crate::formatter::Formatter;
 use crate::function::Function;
-use crate::import::Import;
+use crate::import::{
+    cmp_segments, GroupImports, Import, ImportSettings, ImportsGranularity, UseTree,
+};
 use crate::item::Item;
+use crate::item_ref::{ItemMut, ItemRef};
+use crate::macro_rules::MacroRules;
 use crate::module::Module;
+use crate::parse::Parser;
+use crate::type_alias::TypeAlias;
+use crate::union::Union;
 
+use crate::r#const::Const;
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
+use crate::r#static::Static;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
+use crate::r#type::Type;
 
 /// Defines a scope.
 ///
@@ -22,9 +39,25 @@ pub struct Scope {
     /// Scope documentation
     docs: Option<Docs>,
 
+    /// Inner attributes, e.g., `#![no_std]`.
+    inner_attributes: Vec<String>,
+
+    /// `extern crate` items
+    extern_crates: IndexMap<String, Import>,
+
     /// Imports
     imports: IndexMap<String, IndexMap<String, Import>>,
 
+    /// How `use` statements are grouped, if set for this scope
+    group_imports: Option<GroupImports>,
+
+    /// How imports are merged into `use` statements, if set for this scope
+    imports_granularity: Option<ImportsGranularity>,
+
+    /// Whether qualified type paths are imported automatically, if set for
+    /// this scope
+    auto_import: Option<bool>,
+
     /// Contents of the documentation,
     items: Vec<Item>,
 }
@@ -34,11 +67,35 @@ impl Scope {
     pub fn new() -> Self {
         Scope {
             docs: None,
+            inner_attributes: vec![],
+            extern_crates: IndexMap::new(),
             imports: IndexMap::new(),
+            group_imports: None,
+            imports_granularity: None,
+            auto_import: None,
             items: vec![],
         }
     }
 
+    /// Set the scope documentation.
+    ///
+    /// The documentation is emitted as inner `//!` doc comments at the very
+    /// top of the scope, e.g. to document a generated `lib.rs`.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an inner attribute to the scope, e.g. `no_std` or
+    /// `allow(clippy::all)`.
+    ///
+    /// Inner attributes are emitted after the scope documentation and before
+    /// any imports.
+    pub fn inner_attr(&mut self, attribute: &str) -> &mut Self {
+        self.inner_attributes.push(attribute.to_string());
+        self
+    }
+
     /// Import a type into the scope.
     ///
     /// This results in a new `use` statement being added to the beginning of
@@ -49,11 +106,177 @@ impl Scope {
         let ty = ty.split("::").next().unwrap_or(ty);
         self.imports
             .entry(path.to_string())
-            .or_insert(IndexMap::new())
+            .or_default()
             .entry(ty.to_string())
             .or_insert_with(|| Import::new(path, ty))
     }
 
+    /// Import an item into the scope under a different name, e.g.
+    /// `use std::io::Result as IoResult`.
+    pub fn import_as(&mut self, path: &str, ty: &str, alias: &str) -> &mut Import {
+        self.push_import(Import::new_as(path, ty, alias))
+    }
+
+    /// Import all items of a `use` tree into the scope, e.g.
+    /// `"std::{self, fmt::{Debug, Display}, io::*}"`.
+    ///
+    /// Imports are merged with other imports sharing the tree's path, which is
+    /// the longest path common to all imported items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree cannot be parsed.
+    pub fn import_tree(&mut self, tree: &str) -> &mut Self {
+        self.push_import_tree(None, tree)
+    }
+
+    /// Import all items of a `use` tree into the scope with the given
+    /// visibility, e.g. `pub use self::types::{Request, Response};`.
+    ///
+    /// See [`import_tree`] for how the tree is merged.
+    ///
+    /// [`import_tree`]: #method.import_tree
+    pub fn reexport_tree(&mut self, vis: &str, tree: &str) -> &mut Self {
+        self.push_import_tree(Some(vis), tree)
+    }
+
+    fn push_import_tree(&mut self, vis: Option<&str>, tree: &str) -> &mut Self {
+        let leaves = Parser::parse_all(tree, |p| {
+            let mut leaves = vec![];
+            p.use_tree(&[], &mut leaves)?;
+            Ok(leaves)
+        })
+        .unwrap_or_else(|e| panic!("invalid use tree: {}", e));
+
+        if leaves.is_empty() {
+            return self;
+        }
+
+        // The common path of all imported items
+        let mut len = leaves[0].0.len() - 1;
+
+        for (path, _) in &leaves {
+            len = path[..path.len() - 1]
+                .iter()
+                .zip(&leaves[0].0[..len])
+                .take_while(|(a, b)| a == b)
+                .count();
+        }
+
+        let path = leaves[0].0[..len].join("::");
+
+        for (segments, alias) in &leaves {
+            let name = segments[len..].join("::");
+
+            let mut import = match *alias {
+                Some(alias) => Import::new_as(&path, &name, alias),
+                None => Import::new(&path, &name),
+            };
+
+            if let Some(vis) = vis {
+                import.vis(vis);
+            }
+
+            self.push_import(import);
+        }
+
+        self
+    }
+
+    fn push_import(&mut self, import: Import) -> &mut Import {
+        // Re-exports are kept apart from private imports of the same item
+        let key = match import.vis {
+            Some(ref vis) => format!("{} {}", vis, import.key()),
+            None => import.key(),
+        };
+
+        self.imports
+            .entry(import.path().to_string())
+            .or_default()
+            .entry(key)
+            .or_insert(import)
+    }
+
+    /// Add an `extern crate` item to the scope.
+    pub fn extern_crate(&mut self, name: &str) -> &mut Import {
+        self.extern_crates
+            .entry(name.to_string())
+            .or_insert_with(|| Import::new("", name))
+    }
+
+    /// Add an `extern crate` item to the scope under a different name, e.g.
+    /// `extern crate std as core;`.
+    pub fn extern_crate_as(&mut self, name: &str, alias: &str) -> &mut Import {
+        let import = Import::new_as("", name, alias);
+
+        self.extern_crates.entry(import.key()).or_insert(import)
+    }
+
+    /// Set how `use` statements are grouped and ordered.
+    ///
+    /// The setting applies to this scope and to the modules nested in it,
+    /// unless they set their own. By default, imports are emitted in the
+    /// order they were added.
+    ///
+    /// ```
+    /// use codegen::{GroupImports, Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.group_imports(GroupImports::StdExternalCrate);
+    /// scope.import("crate::model", "User");
+    /// scope.import("std::fmt", "Debug");
+    ///
+    /// assert_eq!(
+    ///     scope.to_string(),
+    ///     "use std::fmt::Debug;\n\nuse crate::model::User;"
+    /// );
+    /// ```
+    pub fn group_imports(&mut self, group: GroupImports) -> &mut Self {
+        self.group_imports = Some(group);
+        self
+    }
+
+    /// Set how imports are merged into `use` statements.
+    ///
+    /// The setting applies to this scope and to the modules nested in it,
+    /// unless they set their own. By default, imports added with the same
+    /// path are merged.
+    pub fn imports_granularity(&mut self, granularity: ImportsGranularity) -> &mut Self {
+        self.imports_granularity = Some(granularity);
+        self
+    }
+
+    /// Set whether qualified type paths are imported automatically.
+    ///
+    /// When enabled, named types written with a path, e.g.
+    /// `std::collections::HashMap`, are imported with a `use` statement and
+    /// referred to by name. A type keeps its full path if its name collides
+    /// with another imported type, an item of the scope, a type used without
+    /// a path or a generic parameter.
+    ///
+    /// The setting applies to this scope and to the modules nested in it,
+    /// unless they set their own. Each module imports the types it uses.
+    ///
+    /// ```
+    /// use codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.set_auto_import(true);
+    /// scope
+    ///     .new_struct("Index")
+    ///     .field("entries", "std::collections::HashMap<String, u32>");
+    ///
+    /// assert_eq!(
+    ///     scope.to_string(),
+    ///     "use std::collections::HashMap;\n\n\
+    ///      struct Index {\n    entries: HashMap<String, u32>,\n}"
+    /// );
+    /// ```
+    pub fn set_auto_import(&mut self, auto_import: bool) -> &mut Self {
+        self.auto_import = Some(auto_import);
+        self
+    }
+
     /// Push a new module definition, returning a mutable reference to it.
     ///
     /// # Panics
@@ -97,7 +320,7 @@ impl Scope {
         self.items
             .iter()
             .filter_map(|item| match item {
-                &Item::Module(ref module) if module.name == *name => Some(module),
+                Item::Module(module) if module.name == *name => Some(module),
                 _ => None,
             })
             .next()
@@ -147,6 +370,22 @@ impl Scope {
         self
     }
 
+    /// Push a new union definition, returning a mutable reference to it.
+    pub fn new_union(&mut self, name: &str) -> &mut Union {
+        self.push_union(Union::new(name));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Union(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a union definition
+    pub fn push_union(&mut self, item: Union) -> &mut Self {
+        self.items.push(Item::Union(item));
+        self
+    }
+
     /// Push a new function definition, returning a mutable reference to it.
     pub fn new_fn(&mut self, name: &str) -> &mut Function {
         self.push_fn(Function::new(name));
@@ -211,6 +450,775 @@ impl Scope {
         self
     }
 
+    /// Push a new type alias definition, returning a mutable reference to it.
+    pub fn new_type_alias<T>(&mut self, name: &str, target: T) -> &mut TypeAlias
+    where
+        T: Into<Type>,
+    {
+        self.push_type_alias(TypeAlias::new(name, target));
+
+        match *self.items.last_mut().unwrap() {
+            Item::TypeAlias(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a type alias definition.
+    pub fn push_type_alias(&mut self, item: TypeAlias) -> &mut Self {
+        self.items.push(Item::TypeAlias(item));
+        self
+    }
+
+    /// Push a new `const` definition, returning a mutable reference to it.
+    pub fn new_const<T>(&mut self, name: &str, ty: T) -> &mut Const
+    where
+        T: Into<Type>,
+    {
+        self.push_const(Const::new(name, ty));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Const(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a `const` definition.
+    pub fn push_const(&mut self, item: Const) -> &mut Self {
+        self.items.push(Item::Const(item));
+        self
+    }
+
+    /// Push a new `static` definition, returning a mutable reference to it.
+    pub fn new_static<T>(&mut self, name: &str, ty: T) -> &mut Static
+    where
+        T: Into<Type>,
+    {
+        self.push_static(Static::new(name, ty));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Static(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a `static` definition.
+    pub fn push_static(&mut self, item: Static) -> &mut Self {
+        self.items.push(Item::Static(item));
+        self
+    }
+
+    /// Push a new `macro_rules!` definition, returning a mutable reference to
+    /// it.
+    pub fn new_macro_rules(&mut self, name: &str) -> &mut MacroRules {
+        self.push_macro_rules(MacroRules::new(name));
+
+        match *self.items.last_mut().unwrap() {
+            Item::MacroRules(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a `macro_rules!` definition.
+    pub fn push_macro_rules(&mut self, item: MacroRules) -> &mut Self {
+        self.items.push(Item::MacroRules(item));
+        self
+    }
+
+    /// Push a new `extern` block using the given ABI, returning a mutable
+    /// reference to it.
+    pub fn new_extern_block(&mut self, abi: &str) -> &mut ExternBlock {
+        self.push_extern_block(ExternBlock::new(abi));
+
+        match *self.items.last_mut().unwrap() {
+            Item::ExternBlock(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push an `extern` block.
+    pub fn push_extern_block(&mut self, item: ExternBlock) -> &mut Self {
+        self.items.push(Item::ExternBlock(item));
+        self
+    }
+
+    /// Returns a reference to a struct with the given name if it exists in this
+    /// scope.
+    pub fn get_struct(&self, name: &str) -> Option<&Struct> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Struct(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a struct with the given name if it exists
+    /// in this scope.
+    pub fn get_struct_mut(&mut self, name: &str) -> Option<&mut Struct> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Struct(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a struct with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_struct(&mut self, name: &str) -> Option<Struct> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Struct(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Struct(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the struct with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_struct(&mut self, item: Struct) -> Option<Struct> {
+        let name = item.name().to_string();
+
+        match self.get_struct_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_struct(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to an enum with the given name if it exists in this
+    /// scope.
+    pub fn get_enum(&self, name: &str) -> Option<&Enum> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Enum(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to an enum with the given name if it exists
+    /// in this scope.
+    pub fn get_enum_mut(&mut self, name: &str) -> Option<&mut Enum> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Enum(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove an enum with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_enum(&mut self, name: &str) -> Option<Enum> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Enum(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Enum(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the enum with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_enum(&mut self, item: Enum) -> Option<Enum> {
+        let name = item.name().to_string();
+
+        match self.get_enum_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_enum(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a union with the given name if it exists in this
+    /// scope.
+    pub fn get_union(&self, name: &str) -> Option<&Union> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Union(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a union with the given name if it exists
+    /// in this scope.
+    pub fn get_union_mut(&mut self, name: &str) -> Option<&mut Union> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Union(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a union with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_union(&mut self, name: &str) -> Option<Union> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Union(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Union(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the union with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_union(&mut self, item: Union) -> Option<Union> {
+        let name = item.name().to_string();
+
+        match self.get_union_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_union(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a function with the given name if it exists in
+    /// this scope.
+    pub fn get_fn(&self, name: &str) -> Option<&Function> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Function(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a function with the given name if it
+    /// exists in this scope.
+    pub fn get_fn_mut(&mut self, name: &str) -> Option<&mut Function> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Function(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a function with the given name from this scope, returning it if
+    /// it existed.
+    pub fn remove_fn(&mut self, name: &str) -> Option<Function> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Function(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Function(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the function with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_fn(&mut self, item: Function) -> Option<Function> {
+        let name = item.name().to_string();
+
+        match self.get_fn_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_fn(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a trait with the given name if it exists in this
+    /// scope.
+    pub fn get_trait(&self, name: &str) -> Option<&Trait> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Trait(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a trait with the given name if it exists
+    /// in this scope.
+    pub fn get_trait_mut(&mut self, name: &str) -> Option<&mut Trait> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Trait(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a trait with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_trait(&mut self, name: &str) -> Option<Trait> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Trait(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Trait(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the trait with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_trait(&mut self, item: Trait) -> Option<Trait> {
+        let name = item.name().to_string();
+
+        match self.get_trait_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_trait(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a type alias with the given name if it exists in
+    /// this scope.
+    pub fn get_type_alias(&self, name: &str) -> Option<&TypeAlias> {
+        self.items.iter().find_map(|item| match *item {
+            Item::TypeAlias(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a type alias with the given name if it
+    /// exists in this scope.
+    pub fn get_type_alias_mut(&mut self, name: &str) -> Option<&mut TypeAlias> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::TypeAlias(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a type alias with the given name from this scope, returning it if
+    /// it existed.
+    pub fn remove_type_alias(&mut self, name: &str) -> Option<TypeAlias> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::TypeAlias(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::TypeAlias(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the type alias with the same name in place, returning the
+    /// previous definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_type_alias(&mut self, item: TypeAlias) -> Option<TypeAlias> {
+        let name = item.name().to_string();
+
+        match self.get_type_alias_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_type_alias(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a const with the given name if it exists in this
+    /// scope.
+    pub fn get_const(&self, name: &str) -> Option<&Const> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Const(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a const with the given name if it exists
+    /// in this scope.
+    pub fn get_const_mut(&mut self, name: &str) -> Option<&mut Const> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Const(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a const with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_const(&mut self, name: &str) -> Option<Const> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Const(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Const(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the const with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_const(&mut self, item: Const) -> Option<Const> {
+        let name = item.name().to_string();
+
+        match self.get_const_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_const(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a static with the given name if it exists in this
+    /// scope.
+    pub fn get_static(&self, name: &str) -> Option<&Static> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Static(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a static with the given name if it exists
+    /// in this scope.
+    pub fn get_static_mut(&mut self, name: &str) -> Option<&mut Static> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Static(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a static with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_static(&mut self, name: &str) -> Option<Static> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Static(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Static(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the static with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_static(&mut self, item: Static) -> Option<Static> {
+        let name = item.name().to_string();
+
+        match self.get_static_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_static(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to a `macro_rules!` definition with the given name
+    /// if it exists in this scope.
+    pub fn get_macro_rules(&self, name: &str) -> Option<&MacroRules> {
+        self.items.iter().find_map(|item| match *item {
+            Item::MacroRules(ref v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to a `macro_rules!` definition with the
+    /// given name if it exists in this scope.
+    pub fn get_macro_rules_mut(&mut self, name: &str) -> Option<&mut MacroRules> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::MacroRules(ref mut v) if v.name() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove a `macro_rules!` definition with the given name from this scope,
+    /// returning it if it existed.
+    pub fn remove_macro_rules(&mut self, name: &str) -> Option<MacroRules> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::MacroRules(ref v) => v.name() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::MacroRules(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the `macro_rules!` definition with the same name in place,
+    /// returning the previous definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_macro_rules(&mut self, item: MacroRules) -> Option<MacroRules> {
+        let name = item.name().to_string();
+
+        match self.get_macro_rules_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_macro_rules(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to an extern block with the given ABI if it exists
+    /// in this scope.
+    pub fn get_extern_block(&self, name: &str) -> Option<&ExternBlock> {
+        self.items.iter().find_map(|item| match *item {
+            Item::ExternBlock(ref v) if v.abi() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to an extern block with the given ABI if it
+    /// exists in this scope.
+    pub fn get_extern_block_mut(&mut self, name: &str) -> Option<&mut ExternBlock> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::ExternBlock(ref mut v) if v.abi() == name => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove an extern block with the given ABI from this scope, returning it
+    /// if it existed.
+    pub fn remove_extern_block(&mut self, name: &str) -> Option<ExternBlock> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::ExternBlock(ref v) => v.abi() == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::ExternBlock(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the extern block with the same ABI in place, returning the
+    /// previous definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_extern_block(&mut self, item: ExternBlock) -> Option<ExternBlock> {
+        let name = item.abi().to_string();
+
+        match self.get_extern_block_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_extern_block(item);
+                None
+            }
+        }
+    }
+
+    /// Remove a module with the given name from this scope, returning it if it
+    /// existed.
+    pub fn remove_module(&mut self, name: &str) -> Option<Module> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Module(ref v) => v.name == name,
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Module(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the module with the same name in place, returning the previous
+    /// definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_module(&mut self, item: Module) -> Option<Module> {
+        let name = item.name.clone();
+
+        match self.get_module_mut(&name) {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_module(item);
+                None
+            }
+        }
+    }
+
+    /// Returns a reference to the impl block of the given trait for the type
+    /// with the given name, or to its inherent impl block if `trait_name` is
+    /// `None`.
+    pub fn get_impl(&self, target: &str, trait_name: Option<&str>) -> Option<&Impl> {
+        self.items.iter().find_map(|item| match *item {
+            Item::Impl(ref v) if v.is_impl_of(target, trait_name) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to the impl block of the given trait for
+    /// the type with the given name, or to its inherent impl block if
+    /// `trait_name` is `None`.
+    pub fn get_impl_mut(&mut self, target: &str, trait_name: Option<&str>) -> Option<&mut Impl> {
+        self.items.iter_mut().find_map(|item| match *item {
+            Item::Impl(ref mut v) if v.is_impl_of(target, trait_name) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Remove the impl block of the given trait for the type with the given
+    /// name, or its inherent impl block if `trait_name` is `None`, returning
+    /// it if it existed.
+    pub fn remove_impl(&mut self, target: &str, trait_name: Option<&str>) -> Option<Impl> {
+        let pos = self.items.iter().position(|item| match *item {
+            Item::Impl(ref v) => v.is_impl_of(target, trait_name),
+            _ => false,
+        })?;
+
+        match self.items.remove(pos) {
+            Item::Impl(v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replace the impl block of the same trait for the same type in place,
+    /// returning the previous definition.
+    ///
+    /// If no such item exists, the new definition is pushed to the scope.
+    pub fn replace_impl(&mut self, item: Impl) -> Option<Impl> {
+        let target = item.target().name().map(str::to_string);
+        let trait_name = item.trait_type().and_then(Type::name).map(str::to_string);
+
+        let existing = match target {
+            Some(ref target) => self.get_impl_mut(target, trait_name.as_deref()),
+            None => None,
+        };
+
+        match existing {
+            Some(existing) => Some(mem::replace(existing, item)),
+            None => {
+                self.push_impl(item);
+                None
+            }
+        }
+    }
+
+    /// Remove all impl blocks for the type with the given name, returning
+    /// them in order.
+    pub fn remove_impls_for(&mut self, name: &str) -> Vec<Impl> {
+        let mut removed = vec![];
+        let mut items = Vec::with_capacity(self.items.len());
+
+        for item in self.items.drain(..) {
+            match item {
+                Item::Impl(v) if v.target().name() == Some(name) => removed.push(v),
+                item => items.push(item),
+            }
+        }
+
+        self.items = items;
+        removed
+    }
+
+    /// Returns an iterator over the impl blocks for the type with the given
+    /// name, e.g. both `impl Foo` and `impl<T> Clone for Foo<T>` for `"Foo"`.
+    pub fn impls_for<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Impl> + 'a {
+        self.items.iter().filter_map(move |item| match *item {
+            Item::Impl(ref v) if v.target().name() == Some(name) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns an iterator over mutable references to the impl blocks for the
+    /// type with the given name.
+    pub fn impls_for_mut<'a>(
+        &'a mut self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a mut Impl> + 'a {
+        self.items.iter_mut().filter_map(move |item| match *item {
+            Item::Impl(ref mut v) if v.target().name() == Some(name) => Some(v),
+            _ => None,
+        })
+    }
+
+    /// Returns a reference to the item at the given path, relative to this
+    /// scope, e.g. `"api::v1::User"`.
+    ///
+    /// All segments but the last name nested modules. If several items in the
+    /// final module share the name, the first one is returned.
+    pub fn find(&self, path: &str) -> Option<ItemRef<'_>> {
+        let segments: Vec<_> = path.split("::").collect();
+        let (name, modules) = segments.split_last()?;
+
+        let mut scope = self;
+
+        for module in modules {
+            scope = &scope.get_module(*module)?.scope;
+        }
+
+        scope
+            .items
+            .iter()
+            .map(ItemRef::new)
+            .find(|item| item.name() == Some(*name))
+    }
+
+    /// Returns a mutable reference to the item at the given path, relative to
+    /// this scope, e.g. `"api::v1::User"`.
+    ///
+    /// See [`find`] for how the path is resolved.
+    ///
+    /// [`find`]: #method.find
+    pub fn find_mut(&mut self, path: &str) -> Option<ItemMut<'_>> {
+        let segments: Vec<_> = path.split("::").collect();
+        let (name, modules) = segments.split_last()?;
+
+        let mut scope = self;
+
+        for module in modules {
+            scope = &mut scope.get_module_mut(*module)?.scope;
+        }
+
+        let pos = scope
+            .items
+            .iter()
+            .position(|item| ItemRef::new(item).name() == Some(*name))?;
+
+        Some(ItemMut::new(&mut scope.items[pos]))
+    }
+
+    /// Returns all items defined in this scope and its nested modules, in
+    /// order, each with the path of the module that contains it.
+    ///
+    /// Items defined directly in this scope have an empty path, and items in
+    /// nested modules have paths such as `"api::v1"`.
+    pub fn walk(&self) -> Vec<(String, ItemRef<'_>)> {
+        let mut items = vec![];
+        self.walk_into("", &mut items);
+        items
+    }
+
+    fn walk_into<'a>(&'a self, path: &str, items: &mut Vec<(String, ItemRef<'a>)>) {
+        for item in &self.items {
+            items.push((path.to_string(), ItemRef::new(item)));
+
+            if let Item::Module(ref module) = *item {
+                let path = if path.is_empty() {
+                    module.name.clone()
+                } else {
+                    format!("{}::{}", path, module.name)
+                };
+
+                module.scope.walk_into(&path, items);
+            }
+        }
+    }
+
     /// Push a raw string to the scope.
     ///
     /// This string will be included verbatim in the formatted string.
@@ -219,42 +1227,272 @@ impl Scope {
         self
     }
 
-    /// Return a string representation of the scope.
-    pub fn to_string(&self) -> String {
-        let mut ret = String::new();
+    /// Return the scope and its file-backed modules as a list of files.
+    ///
+    /// The scope itself is rendered as `root`, e.g. `lib.rs`, `main.rs` or
+    /// `net.rs`. Modules that are not inline are rendered as `foo.rs` or
+    /// `foo/mod.rs`, and their submodules as `foo/bar.rs`.
+    ///
+    /// As in Rust, modules are placed next to a `lib.rs`, `main.rs` or
+    /// `mod.rs` root, and in a directory named after any other root, e.g.
+    /// `net/tcp.rs` for a `net.rs` root. Paths are relative to the directory
+    /// of the root.
+    pub fn to_files<P>(&self, root: P) -> Vec<(PathBuf, String)>
+    where
+        P: AsRef<Path>,
+    {
+        let root = root.as_ref();
 
-        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        let dir = match root.file_stem().and_then(|stem| stem.to_str()) {
+            Some("lib") | Some("main") | Some("mod") | None => root.with_file_name(""),
+            Some(stem) => root.with_file_name(stem),
+        };
 
-        // Remove the trailing newline
-        if ret.as_bytes().last() == Some(&b'\n') {
-            ret.pop();
+        let mut files = vec![(
+            root.to_path_buf(),
+            self.to_file_string(ImportSettings::default()),
+        )];
+        self.push_module_files(&dir, ImportSettings::default(), &mut files);
+        files
+    }
+
+    /// Write the scope and its file-backed modules to the given directory,
+    /// rendering the scope itself as `root`, e.g. `lib.rs`.
+    ///
+    /// See [`to_files`] for how modules are laid out.
+    ///
+    /// [`to_files`]: #method.to_files
+    pub fn write_to_dir<D, P>(&self, dir: D, root: P) -> io::Result<()>
+    where
+        D: AsRef<Path>,
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+
+        for (path, contents) in self.to_files(root) {
+            let path = dir.join(path);
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Push the files of all modules declared in this scope, where `dir` is
+    /// the directory in which the modules' files are placed and `settings`
+    /// are the import settings of the parent scope.
+    pub(crate) fn push_module_files(
+        &self,
+        dir: &Path,
+        settings: ImportSettings,
+        files: &mut Vec<(PathBuf, String)>,
+    ) {
+        let settings = self.import_settings(settings);
+
+        for item in &self.items {
+            if let Item::Module(ref module) = *item {
+                module.push_files(&dir.join(&module.name), settings, files);
+            }
         }
+    }
 
+    /// Return the scope formatted as the contents of a file.
+    pub(crate) fn to_file_string(&self, settings: ImportSettings) -> String {
+        let mut ret = String::new();
+        self.fmt_with(settings, &mut Formatter::new(&mut ret))
+            .unwrap();
         ret
     }
 
+    /// Returns the import settings of the scope, falling back to the settings
+    /// of the parent scope.
+    fn import_settings(&self, parent: ImportSettings) -> ImportSettings {
+        ImportSettings {
+            group: self.group_imports.unwrap_or(parent.group),
+            granularity: self.imports_granularity.unwrap_or(parent.granularity),
+            auto_import: self.auto_import.unwrap_or(parent.auto_import),
+        }
+    }
+
     /// Formats the scope using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        self.fmt_imports(fmt)?;
+        self.fmt_with(ImportSettings::default(), fmt)
+    }
+
+    /// Formats the scope, where `settings` are the import settings of the
+    /// parent scope.
+    pub(crate) fn fmt_with(
+        &self,
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        // Nested modules inherit the import settings of their parent
+        let settings = self.import_settings(settings);
+
+        if settings.auto_import {
+            self.fmt_auto_imported(settings, fmt)
+        } else {
+            self.fmt_contents(&self.imports, &[], settings, fmt)
+        }
+    }
+
+    /// Formats the scope, importing the qualified type paths used by its
+    /// items.
+    ///
+    /// Items are formatted from copies in which the imported paths are
+    /// shortened to the type name. Modules import the paths of their own
+    /// items, so they are formatted as is.
+    fn fmt_auto_imported(&self, settings: ImportSettings, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let mut items: Vec<_> = self
+            .items
+            .iter()
+            .map(|item| match *item {
+                Item::Module(..) | Item::Raw(..) => None,
+                _ => Some(item.clone()),
+            })
+            .collect();
+
+        // Names that are already in use, along with the path they refer to
+        let mut names = HashMap::new();
+
+        for import in self.imports.values().flat_map(|imports| imports.values()) {
+            let path = import.segments().join("::");
+
+            match import.alias() {
+                Some(alias) => names.insert(alias.to_string(), None),
+                None => names.insert(path.rsplit("::").next().unwrap().to_string(), Some(path)),
+            };
+        }
+
+        for item in &self.items {
+            if let Some(name) = ItemRef::new(item).name() {
+                names.insert(name.to_string(), None);
+            }
+        }
+
+        let mut paths = IndexSet::new();
+
+        for item in items.iter_mut().flatten() {
+            item.visit_paths(&mut |path| {
+                if is_qualified_path(path) {
+                    paths.insert(path.clone());
+                } else if is_ident(path) {
+                    // Types used by name and generic parameters would be
+                    // shadowed by an import of the same name
+                    names.entry(path.clone()).or_insert(None);
+                }
+            });
+        }
+
+        // Paths sharing a name cannot all be imported, so none of them are
+        let mut counts = HashMap::new();
 
-        if !self.imports.is_empty() {
-            write!(fmt, "\n")?;
+        for path in &paths {
+            *counts.entry(path.rsplit("::").next().unwrap()).or_insert(0) += 1;
         }
 
+        let mut imports = self.imports.clone();
+        let mut imported = HashSet::new();
+
+        for path in &paths {
+            let (module, name) = path.rsplit_once("::").unwrap();
+
+            if counts[name] > 1 {
+                continue;
+            }
+
+            match names.get(name) {
+                Some(Some(existing)) if existing == path => {}
+                Some(_) => continue,
+                None => {
+                    imports
+                        .entry(module.to_string())
+                        .or_default()
+                        .entry(name.to_string())
+                        .or_insert_with(|| Import::new(module, name));
+                }
+            }
+
+            imported.insert(path.as_str());
+        }
+
+        for item in items.iter_mut().flatten() {
+            item.visit_paths(&mut |path| {
+                if imported.contains(path.as_str()) {
+                    *path = path.rsplit("::").next().unwrap().to_string();
+                }
+            });
+        }
+
+        self.fmt_contents(&imports, &items, settings, fmt)
+    }
+
+    /// Formats the scope with the given imports, where `items` optionally
+    /// replaces the scope's items with a copy, by index.
+    fn fmt_contents(
+        &self,
+        imports: &IndexMap<String, IndexMap<String, Import>>,
+        items: &[Option<Item>],
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt_inner(fmt)?;
+        }
+
+        for attr in &self.inner_attributes {
+            writeln!(fmt, "#![{}]", attr)?;
+        }
+
+        let has_header = self.docs.is_some() || !self.inner_attributes.is_empty();
+
+        let has_imports = !imports.is_empty() || !self.extern_crates.is_empty();
+
+        if has_header && (has_imports || !self.items.is_empty()) {
+            writeln!(fmt)?;
+        }
+
+        self.fmt_imports(imports, settings, fmt)?;
+
+        if has_imports && !self.items.is_empty() {
+            writeln!(fmt)?;
+        }
+
+        self.fmt_items(items, settings, fmt)
+    }
+
+    fn fmt_items(
+        &self,
+        items: &[Option<Item>],
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
         for (i, item) in self.items.iter().enumerate() {
             if i != 0 {
-                write!(fmt, "\n")?;
+                writeln!(fmt)?;
             }
 
+            let item = items.get(i).and_then(Option::as_ref).unwrap_or(item);
+
             match *item {
-                Item::Module(ref v) => v.fmt(fmt)?,
+                Item::Module(ref v) => v.fmt_with(settings, fmt)?,
                 Item::Struct(ref v) => v.fmt(fmt)?,
                 Item::Function(ref v) => v.fmt(false, fmt)?,
                 Item::Trait(ref v) => v.fmt(fmt)?,
                 Item::Enum(ref v) => v.fmt(fmt)?,
                 Item::Impl(ref v) => v.fmt(fmt)?,
+                Item::TypeAlias(ref v) => v.fmt(fmt)?,
+                Item::Const(ref v) => v.fmt(false, fmt)?,
+                Item::Static(ref v) => v.fmt(false, fmt)?,
+                Item::Union(ref v) => v.fmt(fmt)?,
+                Item::MacroRules(ref v) => v.fmt(fmt)?,
+                Item::ExternBlock(ref v) => v.fmt(fmt)?,
                 Item::Raw(ref v) => {
-                    write!(fmt, "{}\n", v)?;
+                    writeln!(fmt, "{}", v)?;
                 }
             }
         }
@@ -262,56 +1500,222 @@ impl Scope {
         Ok(())
     }
 
-    fn fmt_imports(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+    fn fmt_imports(
+        &self,
+        imports: &IndexMap<String, IndexMap<String, Import>>,
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        for import in self.extern_crates.values() {
+            import.fmt_extern_crate(fmt)?;
+        }
+
+        if settings.group != GroupImports::Preserve
+            || settings.granularity != ImportsGranularity::Preserve
+        {
+            return Self::fmt_sorted_imports(imports, settings, fmt);
+        }
+
         // First, collect all visibilities
         let mut visibilities = vec![];
 
-        for (_, imports) in &self.imports {
-            for (_, import) in imports {
+        for (_, path_imports) in imports {
+            for (_, import) in path_imports {
                 if !visibilities.contains(&import.vis) {
                     visibilities.push(import.vis.clone());
                 }
             }
         }
 
-        let mut tys = vec![];
-
         // Loop over all visibilities and format the associated imports
         for vis in &visibilities {
-            for (path, imports) in &self.imports {
-                tys.clear();
+            for (path, path_imports) in imports {
+                let mut tree = UseTree::default();
 
-                for (ty, import) in imports {
+                for import in path_imports.values() {
                     if *vis == import.vis {
-                        tys.push(ty);
+                        tree.insert(import);
                     }
                 }
 
-                if !tys.is_empty() {
+                if !tree.is_empty() {
                     if let Some(ref vis) = *vis {
                         write!(fmt, "{} ", vis)?;
                     }
 
-                    write!(fmt, "use {}::", path)?;
+                    write!(fmt, "use ")?;
+
+                    if !path.is_empty() {
+                        write!(fmt, "{}::", path)?;
+                    }
+
+                    tree.fmt(fmt)?;
+                    writeln!(fmt, ";")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Formats the imports merged, grouped and sorted according to the
+    /// scope's import settings.
+    fn fmt_sorted_imports(
+        imports: &IndexMap<String, IndexMap<String, Import>>,
+        settings: ImportSettings,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        // Each statement is keyed by group, visibility and path prefix
+        let mut statements: Vec<(usize, &Option<String>, Vec<&str>, UseTree)> = vec![];
+        let mut visibilities = vec![];
+
+        for import in imports.values().flat_map(|imports| imports.values()) {
+            let segments = import.segments();
 
-                    if tys.len() > 1 {
-                        write!(fmt, "{{")?;
+            let split = match settings.granularity {
+                ImportsGranularity::Preserve => {
+                    import.path().split("::").filter(|s| !s.is_empty()).count()
+                }
+                ImportsGranularity::Crate => 1,
+                ImportsGranularity::Module | ImportsGranularity::Item => segments.len() - 1,
+                ImportsGranularity::One => 0,
+            };
+
+            let (prefix, rest) = segments.split_at(split.min(segments.len()));
+            let rest = if rest.is_empty() {
+                "self".to_string()
+            } else {
+                rest.join("::")
+            };
+
+            let group = match settings.group {
+                GroupImports::StdExternalCrate => match segments[0] {
+                    "std" | "core" | "alloc" => 0,
+                    "self" | "super" | "crate" => 2,
+                    _ => 1,
+                },
+                _ => 0,
+            };
+
+            if !visibilities.contains(&&import.vis) {
+                visibilities.push(&import.vis);
+            }
+
+            let existing = statements.iter_mut().find(|(g, vis, p, _)| {
+                settings.granularity != ImportsGranularity::Item
+                    && *g == group
+                    && **vis == import.vis
+                    && p[..] == *prefix
+            });
 
-                        for (i, ty) in tys.iter().enumerate() {
-                            if i != 0 {
-                                write!(fmt, ", ")?;
-                            }
-                            write!(fmt, "{}", ty)?;
+            match existing {
+                Some((_, _, _, tree)) => tree.insert_path(&rest, import.alias()),
+                None => {
+                    let mut tree = UseTree::default();
+                    tree.insert_path(&rest, import.alias());
+                    statements.push((group, &import.vis, prefix.to_vec(), tree));
+                }
+            }
+        }
+
+        for (_, _, _, tree) in &mut statements {
+            tree.sort();
+        }
+
+        statements.sort_by(|a, b| {
+            let vis = |s: &(_, &Option<String>, _, _)| visibilities.iter().position(|v| *v == s.1);
+
+            a.0.cmp(&b.0)
+                .then_with(|| vis(a).cmp(&vis(b)))
+                .then_with(|| {
+                    let a = a.2.iter().copied().chain(Some(a.3.first_name()));
+                    let mut b = b.2.iter().copied().chain(Some(b.3.first_name()));
+
+                    for a in a {
+                        match b.next() {
+                            Some(b) => match cmp_segments(a, b) {
+                                Ordering::Equal => {}
+                                ordering => return ordering,
+                            },
+                            None => return Ordering::Greater,
                         }
+                    }
 
-                        write!(fmt, "}};\n")?;
-                    } else if tys.len() == 1 {
-                        write!(fmt, "{};\n", tys[0])?;
+                    if b.next().is_some() {
+                        Ordering::Less
+                    } else {
+                        Ordering::Equal
                     }
-                }
+                })
+        });
+
+        for (i, (group, vis, prefix, tree)) in statements.iter().enumerate() {
+            if i != 0 && statements[i - 1].0 != *group {
+                writeln!(fmt)?;
+            }
+
+            if let Some(ref vis) = **vis {
+                write!(fmt, "{} ", vis)?;
             }
+
+            write!(fmt, "use ")?;
+
+            // A lone `self` import is the path itself
+            if tree.is_self() {
+                writeln!(fmt, "{};", prefix.join("::"))?;
+                continue;
+            }
+
+            for segment in prefix {
+                write!(fmt, "{}::", segment)?;
+            }
+
+            tree.fmt(fmt)?;
+            writeln!(fmt, ";")?;
         }
 
         Ok(())
     }
 }
+
+impl Default for Scope {
+    fn default() -> Self {
+        Scope::new()
+    }
+}
+
+/// Formats the scope as a string, without a trailing newline.
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+
+        self.fmt(&mut Formatter::new(&mut ret))?;
+
+        // Remove the trailing newline
+        if ret.as_bytes().last() == Some(&b'\n') {
+            ret.pop();
+        }
+
+        f.write_str(&ret)
+    }
+}
+
+/// Returns `true` if the given type path can be imported, e.g.
+/// `std::collections::HashMap`, but not `HashMap`, `Self::Item` or `T::Output`.
+fn is_qualified_path(path: &str) -> bool {
+    let segments: Vec<_> = path.split("::").collect();
+    let (name, modules) = segments.split_last().unwrap();
+
+    !modules.is_empty()
+        && is_ident(name)
+        && modules
+            .iter()
+            .all(|m| is_ident(m) && m.starts_with(|c: char| c.is_lowercase() || c == '_'))
+}
+
+/// Returns `true` if the given path is a single identifier, e.g. `HashMap`.
+fn is_ident(segment: &str) -> bool {
+    !segment.is_empty()
+        && !segment.starts_with(|c: char| c.is_ascii_digit())
+        && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
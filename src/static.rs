@@ -0,0 +1,185 @@
+use std::fmt::{self, Write};
+
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+
+use crate::r#type::Type;
+
+/// Defines a `static` item.
+#[derive(Debug, Clone)]
+pub struct Static {
+    /// Name of the static
+    name: String,
+
+    /// Static documentation
+    docs: Option<Docs>,
+
+    /// Static visibility
+    vis: Option<String>,
+
+    /// Static attributes, e.g., `#[no_mangle]`.
+    attributes: Vec<String>,
+
+    /// Whether or not the static is `mut`
+    r#mut: bool,
+
+    /// Whether or not the foreign static is declared `safe`
+    safe: bool,
+
+    /// Whether or not the foreign static is declared `unsafe`
+    r#unsafe: bool,
+
+    /// Static type
+    ty: Type,
+
+    /// Initializer expression
+    value: Option<String>,
+}
+
+impl Static {
+    /// Return a new static definition with the provided name and type.
+    pub fn new<T>(name: &str, ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Static {
+            name: name.to_string(),
+            docs: None,
+            vis: None,
+            attributes: vec![],
+            r#mut: false,
+            safe: false,
+            r#unsafe: false,
+            ty: ty.into(),
+            value: None,
+        }
+    }
+
+    /// Returns the static name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a reference to the static type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Set the static documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Set the static visibility.
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add an attribute to the static.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Set whether this static is `mut` or not.
+    pub fn set_mut(&mut self, r#mut: bool) -> &mut Self {
+        self.r#mut = r#mut;
+        self
+    }
+
+    /// Set whether this foreign static is declared `safe` or not.
+    ///
+    /// Only statics in an `unsafe extern` block can be declared `safe`.
+    pub fn set_safe(&mut self, safe: bool) -> &mut Self {
+        self.safe = safe;
+        self
+    }
+
+    /// Set whether this foreign static is declared `unsafe` or not.
+    ///
+    /// Only statics in an `unsafe extern` block can be declared `unsafe`.
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Returns `true` if the static is declared `safe` or `unsafe`.
+    pub(crate) fn has_safety(&self) -> bool {
+        self.safe || self.r#unsafe
+    }
+
+    /// Set the initializer expression of the static.
+    pub fn value<T>(&mut self, value: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.value = Some(value.to_string());
+        self
+    }
+
+    /// Call `f` with the type paths used by the static.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.ty.visit_paths(f);
+    }
+
+    /// Formats the static using the given formatter.
+    ///
+    /// Statics declared in an `extern` block do not have a value.
+    pub fn fmt(&self, is_foreign: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        if let Some(ref vis) = self.vis {
+            write!(fmt, "{} ", vis)?;
+        }
+
+        if self.has_safety() {
+            assert!(
+                is_foreign,
+                "only foreign statics can be declared `safe` or `unsafe`"
+            );
+            assert!(
+                !(self.safe && self.r#unsafe),
+                "statics cannot be both `safe` and `unsafe`"
+            );
+        }
+
+        if self.safe {
+            write!(fmt, "safe ")?;
+        }
+
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
+        }
+
+        write!(fmt, "static ")?;
+
+        if self.r#mut {
+            write!(fmt, "mut ")?;
+        }
+
+        write!(fmt, "{}: ", self.name)?;
+        self.ty.fmt(fmt)?;
+
+        match self.value {
+            Some(ref value) => {
+                assert!(!is_foreign, "foreign statics cannot have a value");
+                writeln!(fmt, " = {};", value)
+            }
+            None => {
+                if !is_foreign {
+                    panic!("statics must have a value");
+                }
+
+                writeln!(fmt, ";")
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use std::fmt::{self, Write};
 
+use crate::bound::Bound;
 use crate::field::Field;
 use crate::fields::Fields;
 use crate::formatter::Formatter;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 use crate::type_def::TypeDef;
 
 use crate::r#type::Type;
@@ -25,6 +28,11 @@ impl Struct {
         }
     }
 
+    /// Returns the structure name.
+    pub fn name(&self) -> &str {
+        self.type_def.name()
+    }
+
     /// Returns a reference to the type
     pub fn ty(&self) -> &Type {
         &self.type_def.ty
@@ -37,20 +45,37 @@ impl Struct {
     }
 
     /// Add a generic to the struct.
+    ///
+    /// The generic is parsed, so it may be a lifetime, include inline bounds
+    /// and a default, or be a comma separated list, e.g. `"'a, T: Clone"`.
     pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.type_def.ty.generic(name);
+        self.type_def.generic(name);
+        self
+    }
+
+    /// Push a generic parameter to the struct.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        self.type_def.push_generic(param);
         self
     }
 
     /// Add a `where` bound to the struct.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
     pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
         self.type_def.bound(name, ty);
         self
     }
 
+    /// Push a `where` predicate to the struct.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        self.type_def.push_bound(bound);
+        self
+    }
+
     /// Set the structure documentation.
     pub fn doc(&mut self, docs: &str) -> &mut Self {
         self.type_def.doc(docs);
@@ -108,21 +133,27 @@ impl Struct {
         self
     }
 
+    /// Call `f` with the type paths used by the struct, along with the names
+    /// of its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.type_def.visit_paths(f);
+        self.fields.visit_paths(f);
+    }
+
     /// Formats the struct using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        self.type_def.fmt_head("struct", &[], fmt)?;
-        self.fields.fmt(fmt)?;
-
         match self.fields {
-            Fields::Empty => {
-                write!(fmt, ";\n")?;
+            Fields::Named(..) => {
+                self.type_def.fmt_head("struct", &[], fmt)?;
+                self.fields.fmt(fmt)
             }
-            Fields::Tuple(..) => {
-                write!(fmt, ";\n")?;
+            // The `where` clause of unit and tuple structs follows the fields
+            Fields::Empty | Fields::Tuple(..) => {
+                self.type_def.fmt_signature("struct", &[], fmt)?;
+                self.fields.fmt(fmt)?;
+                self.type_def.fmt_trailing_bounds(fmt)?;
+                writeln!(fmt, ";")
             }
-            _ => {}
         }
-
-        Ok(())
     }
 }
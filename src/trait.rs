@@ -2,20 +2,25 @@ use std::fmt::{self, Write};
 
 use crate::associated_type::AssociatedType;
 use crate::bound::Bound;
-use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::formatter::Formatter;
 use crate::function::Function;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 use crate::type_def::TypeDef;
 
+use crate::r#const::Const;
 use crate::r#type::Type;
 
 /// Define a trait.
 #[derive(Debug, Clone)]
 pub struct Trait {
     type_def: TypeDef,
-    parents: Vec<Type>,
+    parents: Vec<TypeBound>,
     associated_tys: Vec<AssociatedType>,
+    consts: Vec<Const>,
     fns: Vec<Function>,
-    macros: Vec<String>,
+    r#unsafe: bool,
+    auto: bool,
 }
 
 impl Trait {
@@ -25,11 +30,18 @@ impl Trait {
             type_def: TypeDef::new(name),
             parents: vec![],
             associated_tys: vec![],
+            consts: vec![],
             fns: vec![],
-            macros: vec![],
+            r#unsafe: false,
+            auto: false,
         }
     }
 
+    /// Returns the trait name.
+    pub fn name(&self) -> &str {
+        self.type_def.name()
+    }
+
     /// Returns a reference to the type
     pub fn ty(&self) -> &Type {
         &self.type_def.ty
@@ -43,31 +55,65 @@ impl Trait {
 
     /// Add a generic to the trait
     pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.type_def.ty.generic(name);
+        self.type_def.generic(name);
+        self
+    }
+
+    /// Push a generic parameter to the trait.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        self.type_def.push_generic(param);
         self
     }
 
     /// Add a `where` bound to the trait.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
     pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
         self.type_def.bound(name, ty);
         self
     }
 
+    /// Push a `where` predicate to the trait.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        self.type_def.push_bound(bound);
+        self
+    }
+
     /// Add a macro to the trait def (e.g. `"#[async_trait]"`)
     pub fn r#macro(&mut self, r#macro: &str) -> &mut Self {
         self.type_def.r#macro(r#macro);
         self
     }
 
-    /// Add a parent trait.
+    /// Add an attribute to the trait.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.type_def.attr(attribute);
+        self
+    }
+
+    /// Set whether this trait is unsafe to implement or not.
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Set whether this trait is an `auto` trait or not.
+    ///
+    /// Auto traits require the unstable `auto_traits` feature.
+    pub fn set_auto(&mut self, auto: bool) -> &mut Self {
+        self.auto = auto;
+        self
+    }
+
+    /// Add a parent trait, e.g. `Clone`, `?Sized` or `'static`.
     pub fn parent<T>(&mut self, ty: T) -> &mut Self
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
-        self.parents.push(ty.into());
+        TypeBound::push(&mut self.parents, ty.into());
         self
     }
 
@@ -80,15 +126,38 @@ impl Trait {
     /// Add an associated type. Returns a mutable reference to the new
     /// associated type for futher configuration.
     pub fn associated_type(&mut self, name: &str) -> &mut AssociatedType {
-        self.associated_tys.push(AssociatedType(Bound {
-            name: name.to_string(),
-            bound: vec![],
-        }));
-
+        self.push_associated_type(AssociatedType::new(name));
         self.associated_tys.last_mut().unwrap()
     }
 
-    /// Push a new function definition, returning a mutable reference to it.
+    /// Push an associated type.
+    pub fn push_associated_type(&mut self, item: AssociatedType) -> &mut Self {
+        self.associated_tys.push(item);
+        self
+    }
+
+    /// Push a new associated constant, returning a mutable reference to it.
+    ///
+    /// The constant's value is optional and acts as a default for
+    /// implementors.
+    pub fn new_const<T>(&mut self, name: &str, ty: T) -> &mut Const
+    where
+        T: Into<Type>,
+    {
+        self.push_const(Const::new(name, ty));
+        self.consts.last_mut().unwrap()
+    }
+
+    /// Push an associated constant.
+    pub fn push_const(&mut self, item: Const) -> &mut Self {
+        self.consts.push(item);
+        self
+    }
+
+    /// Push a new required function declaration, returning a mutable reference
+    /// to it.
+    ///
+    /// Required functions have no body and must be defined by implementors.
     pub fn new_fn(&mut self, name: &str) -> &mut Function {
         let mut func = Function::new(name);
         func.body = None;
@@ -97,38 +166,71 @@ impl Trait {
         self.fns.last_mut().unwrap()
     }
 
+    /// Push a new provided function, returning a mutable reference to it.
+    ///
+    /// Provided functions have a default body which implementors may
+    /// override.
+    pub fn new_provided_fn(&mut self, name: &str) -> &mut Function {
+        self.push_fn(Function::new(name));
+        self.fns.last_mut().unwrap()
+    }
+
     /// Push a function definition.
+    ///
+    /// The function is provided if it has a body, and required otherwise.
     pub fn push_fn(&mut self, item: Function) -> &mut Self {
         self.fns.push(item);
         self
     }
 
+    /// Call `f` with the type paths used by the trait, along with the names
+    /// of its generic parameters and those of its functions.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.type_def.visit_paths(f);
+
+        for parent in &mut self.parents {
+            parent.visit_paths(f);
+        }
+
+        for ty in &mut self.associated_tys {
+            ty.visit_paths(f);
+        }
+
+        for item in &mut self.consts {
+            item.visit_paths(f);
+        }
+
+        for func in &mut self.fns {
+            func.visit_paths(f);
+        }
+    }
+
     /// Formats the scope using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        self.type_def.fmt_head("trait", &self.parents, fmt)?;
+        let keyword = match (self.r#unsafe, self.auto) {
+            (false, false) => "trait",
+            (true, false) => "unsafe trait",
+            (false, true) => "auto trait",
+            (true, true) => "unsafe auto trait",
+        };
+
+        self.type_def.fmt_head(keyword, &self.parents, fmt)?;
 
         fmt.block(|fmt| {
             let assoc = &self.associated_tys;
 
             // format associated types
-            if !assoc.is_empty() {
-                for ty in assoc {
-                    let ty = &ty.0;
-
-                    write!(fmt, "type {}", ty.name)?;
-
-                    if !ty.bound.is_empty() {
-                        write!(fmt, ": ")?;
-                        fmt_bound_rhs(&ty.bound, fmt)?;
-                    }
+            for ty in assoc {
+                ty.fmt(true, fmt)?;
+            }
 
-                    write!(fmt, ";\n")?;
-                }
+            for c in &self.consts {
+                c.fmt(true, fmt)?;
             }
 
             for (i, func) in self.fns.iter().enumerate() {
-                if i != 0 || !assoc.is_empty() {
-                    write!(fmt, "\n")?;
+                if i != 0 || !assoc.is_empty() || !self.consts.is_empty() {
+                    writeln!(fmt)?;
                 }
 
                 func.fmt(true, fmt)?;
@@ -1,70 +1,755 @@
 use std::fmt::{self, Write};
+use std::str::FromStr;
 
-use crate::formatter::Formatter;
+use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::parse::{ParseError, Parser};
+use crate::type_bound::TypeBound;
 
 /// Defines a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Type {
-    name: String,
-    generics: Vec<Type>,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    /// A named type, e.g. `std::vec::Vec<T>`
+    Path {
+        name: String,
+        generics: Vec<GenericArg>,
+        /// Whether the generics are written with a turbofish, e.g. `Vec::<T>`
+        turbofish: bool,
+    },
+    /// `&'a mut T`
+    Reference {
+        lifetime: Option<String>,
+        mutable: bool,
+        ty: Box<Type>,
+    },
+    /// `*const T` or `*mut T`
+    Pointer { mutable: bool, ty: Box<Type> },
+    /// `[T]`
+    Slice(Box<Type>),
+    /// `[T; N]`
+    Array { ty: Box<Type>, len: String },
+    /// `(A, B)`
+    Tuple(Vec<Type>),
+    /// `Fn(A, B) -> R`
+    Parenthesized {
+        name: String,
+        args: Vec<Type>,
+        ret: Option<Box<Type>>,
+    },
+    /// `for<'a> unsafe extern "C" fn(A, B) -> R`
+    FnPtr {
+        qualifiers: Box<FnQualifiers>,
+        args: Vec<Type>,
+        ret: Option<Box<Type>>,
+    },
+    /// `dyn A + B`
+    DynTrait(Vec<TypeBound>),
+    /// `impl A + B`
+    ImplTrait(Vec<TypeBound>),
+    /// `!`
+    Never,
+    /// `_`
+    Infer,
+}
+
+/// Qualifiers of a function pointer type, boxed as they are rarely set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct FnQualifiers {
+    /// Lifetimes of the `for<...>` binder
+    lifetimes: Vec<String>,
+
+    /// Whether or not the function pointer is `unsafe`
+    r#unsafe: bool,
+
+    /// ABI of the function pointer, empty for a bare `extern`
+    abi: Option<String>,
+}
+
+/// Defines a generic argument of a type, e.g. the `T` in `Vec<T>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericArg {
+    kind: ArgKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ArgKind {
+    Type(Type),
+    Lifetime(String),
+    /// A const argument, e.g. `4` or `{ N * 2 }`
+    Const(String),
+    /// An associated type binding, e.g. `Item = T`
+    Binding(String, Type),
 }
 
 impl Type {
     /// Return a new type with the given name.
+    ///
+    /// The name is parsed, so it may include a path and generics, e.g.
+    /// `std::collections::HashMap<String, Vec<u8>>`. If the name cannot be
+    /// parsed, it is used verbatim.
     pub fn new(name: &str) -> Self {
-        Type {
-            name: name.to_string(),
+        Type::from(name)
+    }
+
+    pub(crate) fn new_path(name: String) -> Self {
+        Type::from_kind(Kind::Path {
+            name,
             generics: vec![],
+            turbofish: false,
+        })
+    }
+
+    pub(crate) fn set_turbofish(&mut self, value: bool) {
+        if let Kind::Path {
+            ref mut turbofish, ..
+        } = self.kind
+        {
+            *turbofish = value;
+        }
+    }
+
+    pub(crate) fn parenthesized(name: &str, args: Vec<Type>, ret: Option<Type>) -> Self {
+        Type::from_kind(Kind::Parenthesized {
+            name: name.to_string(),
+            args,
+            ret: ret.map(Box::new),
+        })
+    }
+
+    /// Return the `Self` type.
+    pub fn self_type() -> Self {
+        Type::new("Self")
+    }
+
+    /// Return the never type, `!`.
+    pub fn never() -> Self {
+        Type::from_kind(Kind::Never)
+    }
+
+    /// Return the inferred type, `_`.
+    pub fn infer() -> Self {
+        Type::from_kind(Kind::Infer)
+    }
+
+    /// Return the unit type, `()`.
+    pub fn unit() -> Self {
+        Type::from_kind(Kind::Tuple(vec![]))
+    }
+
+    /// Return a shared reference to the given type, e.g. `&T`.
+    pub fn reference<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Reference {
+            lifetime: None,
+            mutable: false,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Return a mutable reference to the given type, e.g. `&mut T`.
+    pub fn mut_reference<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Reference {
+            lifetime: None,
+            mutable: true,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Return a `*const` raw pointer to the given type.
+    pub fn ptr<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Pointer {
+            mutable: false,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Return a `*mut` raw pointer to the given type.
+    pub fn mut_ptr<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Pointer {
+            mutable: true,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Return a slice of the given element type, e.g. `[T]`.
+    pub fn slice<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Slice(Box::new(ty.into())))
+    }
+
+    /// Return an array of the given element type and length expression,
+    /// e.g. `[T; 16]`.
+    pub fn array<T, L>(ty: T, len: L) -> Self
+    where
+        T: Into<Type>,
+        L: ToString,
+    {
+        Type::from_kind(Kind::Array {
+            ty: Box::new(ty.into()),
+            len: len.to_string(),
+        })
+    }
+
+    /// Return a tuple of the given types, e.g. `(A, B)`.
+    pub fn tuple<I, T>(tys: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::Tuple(tys.into_iter().map(Into::into).collect()))
+    }
+
+    /// Return a function pointer taking the given argument types, e.g.
+    /// `fn(A, B)`.
+    ///
+    /// Use `ret` to set the return type.
+    pub fn fn_ptr<I, T>(args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Type>,
+    {
+        Type::from_kind(Kind::FnPtr {
+            qualifiers: Box::default(),
+            args: args.into_iter().map(Into::into).collect(),
+            ret: None,
+        })
+    }
+
+    /// Return a trait object with the given bounds, e.g. `dyn Fn() + Send`.
+    pub fn dyn_trait<I, T>(bounds: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<TypeBound>,
+    {
+        Type::from_kind(Kind::DynTrait(bounds.into_iter().map(Into::into).collect()))
+    }
+
+    /// Return an `impl Trait` type with the given bounds, e.g.
+    /// `impl Iterator<Item = T>`.
+    pub fn impl_trait<I, T>(bounds: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<TypeBound>,
+    {
+        Type::from_kind(Kind::ImplTrait(
+            bounds.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    fn from_kind(kind: Kind) -> Self {
+        Type { kind }
+    }
+
+    /// Returns the name of the type if it is a named type.
+    pub fn name(&self) -> Option<&str> {
+        match self.kind {
+            Kind::Path { ref name, .. } | Kind::Parenthesized { ref name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the generic arguments of the type.
+    pub fn generics(&self) -> &[GenericArg] {
+        match self.kind {
+            Kind::Path { ref generics, .. } => generics,
+            _ => &[],
+        }
+    }
+
+    /// Returns a mutable reference to the generic arguments of the type.
+    ///
+    /// The generic arguments are written after the type name, even if the
+    /// name could not be parsed and is used verbatim.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a named type.
+    pub fn generics_mut(&mut self) -> &mut Vec<GenericArg> {
+        match self.kind {
+            Kind::Path {
+                ref mut generics, ..
+            } => generics,
+            _ => panic!("only named types have generics"),
         }
     }
 
     /// Add a generic to the type.
-    pub fn generic<T>(&mut self, ty: T) -> &mut Self
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a named type.
+    pub fn generic<T>(&mut self, arg: T) -> &mut Self
+    where
+        T: Into<GenericArg>,
+    {
+        self.generics_mut().push(arg.into());
+        self
+    }
+
+    /// Add a const argument to the type, e.g. `{ N * 2 }`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a named type.
+    pub fn const_generic<T>(&mut self, expr: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.generics_mut().push(GenericArg::constant(expr));
+        self
+    }
+
+    /// Add an associated type binding to the type, e.g. `Item = T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a named type.
+    pub fn binding<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.generics_mut().push(GenericArg::binding(name, ty));
+        self
+    }
+
+    /// Set the lifetime of a reference type, e.g. `&'a T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a reference.
+    pub fn lifetime(&mut self, lifetime: &str) -> &mut Self {
+        match self.kind {
+            Kind::Reference {
+                lifetime: ref mut lt,
+                ..
+            } => *lt = Some(lifetime.to_string()),
+            _ => panic!("only reference types have a lifetime"),
+        }
+
+        self
+    }
+
+    /// Set the return type of a function pointer type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a function pointer.
+    pub fn ret<T>(&mut self, ty: T) -> &mut Self
     where
         T: Into<Type>,
     {
-        // Make sure that the name doesn't already include generics
-        assert!(
-            !self.name.contains("<"),
-            "type name already includes generics"
-        );
+        match self.kind {
+            Kind::FnPtr { ref mut ret, .. } | Kind::Parenthesized { ref mut ret, .. } => {
+                *ret = Some(Box::new(ty.into()))
+            }
+            _ => panic!("only function pointer types have a return type"),
+        }
+
+        self
+    }
+
+    /// Add a lifetime to the `for<...>` binder of a function pointer type,
+    /// e.g. `for<'a> fn(&'a u8)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a function pointer.
+    pub fn for_lifetime(&mut self, lifetime: &str) -> &mut Self {
+        match self.kind {
+            Kind::FnPtr {
+                ref mut qualifiers, ..
+            } => qualifiers.lifetimes.push(lifetime.to_string()),
+            _ => panic!("only function pointer types have a `for<...>` binder"),
+        }
+
+        self
+    }
+
+    /// Set whether a function pointer type is `unsafe`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a function pointer.
+    pub fn set_unsafe(&mut self, value: bool) -> &mut Self {
+        match self.kind {
+            Kind::FnPtr {
+                ref mut qualifiers, ..
+            } => qualifiers.r#unsafe = value,
+            _ => panic!("only function pointer types can be `unsafe`"),
+        }
+
+        self
+    }
+
+    /// Set the `extern` ABI of a function pointer type, e.g. `"C"`.
+    ///
+    /// An empty ABI is written as a bare `extern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a function pointer.
+    pub fn extern_abi(&mut self, abi: &str) -> &mut Self {
+        match self.kind {
+            Kind::FnPtr {
+                ref mut qualifiers, ..
+            } => qualifiers.abi = Some(abi.to_string()),
+            _ => panic!("only function pointer types have an ABI"),
+        }
+
+        self
+    }
+
+    /// Add a bound to a `dyn` or `impl` trait type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a `dyn` or `impl` trait type.
+    pub fn bound<T>(&mut self, bound: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        match self.kind {
+            Kind::DynTrait(ref mut bounds) | Kind::ImplTrait(ref mut bounds) => {
+                TypeBound::push(bounds, bound.into())
+            }
+            _ => panic!("only `dyn` and `impl` trait types have bounds"),
+        }
 
-        self.generics.push(ty.into());
         self
     }
 
-    /// Rewrite the `Type` with the provided path
+    /// Rewrite the `Type` with the provided path prepended to its name.
+    ///
+    /// # Panics
     ///
-    /// TODO: Is this needed?
+    /// Panics if the type is not a named type.
     pub fn path(&self, path: &str) -> Type {
-        // TODO: This isn't really correct
-        assert!(!self.name.contains("::"));
+        let mut ty = self.clone();
 
-        let mut name = path.to_string();
-        name.push_str("::");
-        name.push_str(&self.name);
+        match ty.kind {
+            Kind::Path { ref mut name, .. } | Kind::Parenthesized { ref mut name, .. } => {
+                name.insert_str(0, "::");
+                name.insert_str(0, path.trim_end_matches("::"));
+            }
+            _ => panic!("only named types have a path"),
+        }
 
-        Type {
-            name,
-            generics: self.generics.clone(),
+        ty
+    }
+
+    /// Call `f` with the path of every named type within the type, e.g. both
+    /// `std::vec::Vec` and `T` for `std::vec::Vec<T>`.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match self.kind {
+            Kind::Path {
+                ref mut name,
+                ref mut generics,
+                ..
+            } => {
+                f(name);
+
+                for arg in generics {
+                    arg.visit_paths(f);
+                }
+            }
+            Kind::Reference { ref mut ty, .. }
+            | Kind::Pointer { ref mut ty, .. }
+            | Kind::Slice(ref mut ty)
+            | Kind::Array { ref mut ty, .. } => ty.visit_paths(f),
+            Kind::Tuple(ref mut tys) => {
+                for ty in tys {
+                    ty.visit_paths(f);
+                }
+            }
+            Kind::Parenthesized {
+                ref mut name,
+                ref mut args,
+                ref mut ret,
+            } => {
+                f(name);
+                Type::visit_fn_paths(args, ret, f);
+            }
+            Kind::FnPtr {
+                ref mut args,
+                ref mut ret,
+                ..
+            } => Type::visit_fn_paths(args, ret, f),
+            Kind::DynTrait(ref mut bounds) | Kind::ImplTrait(ref mut bounds) => {
+                for bound in bounds {
+                    bound.visit_paths(f);
+                }
+            }
+            Kind::Never | Kind::Infer => {}
+        }
+    }
+
+    fn visit_fn_paths(
+        args: &mut [Type],
+        ret: &mut Option<Box<Type>>,
+        f: &mut dyn FnMut(&mut String),
+    ) {
+        for arg in args {
+            arg.visit_paths(f);
+        }
+
+        if let Some(ref mut ret) = *ret {
+            ret.visit_paths(f);
         }
     }
 
     /// Formats the struct using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        write!(fmt, "{}", self.name)?;
-        Type::fmt_slice(&self.generics, fmt)
+        match self.kind {
+            Kind::Path {
+                ref name,
+                ref generics,
+                turbofish,
+            } => {
+                write!(fmt, "{}", name)?;
+
+                if turbofish && !generics.is_empty() {
+                    write!(fmt, "::")?;
+                }
+
+                GenericArg::fmt_slice(generics, fmt)
+            }
+            Kind::Reference {
+                ref lifetime,
+                mutable,
+                ref ty,
+            } => {
+                write!(fmt, "&")?;
+
+                if let Some(ref lifetime) = *lifetime {
+                    write!(fmt, "{} ", lifetime)?;
+                }
+
+                if mutable {
+                    write!(fmt, "mut ")?;
+                }
+
+                ty.fmt_nested(fmt)
+            }
+            Kind::Pointer { mutable, ref ty } => {
+                if mutable {
+                    write!(fmt, "*mut ")?;
+                } else {
+                    write!(fmt, "*const ")?;
+                }
+
+                ty.fmt_nested(fmt)
+            }
+            Kind::Slice(ref ty) => {
+                write!(fmt, "[")?;
+                ty.fmt(fmt)?;
+                write!(fmt, "]")
+            }
+            Kind::Array { ref ty, ref len } => {
+                write!(fmt, "[")?;
+                ty.fmt(fmt)?;
+                write!(fmt, "; {}]", len)
+            }
+            Kind::Tuple(ref tys) if tys.len() == 1 => {
+                // A single element tuple requires a trailing comma
+                write!(fmt, "(")?;
+                tys[0].fmt(fmt)?;
+                write!(fmt, ",)")
+            }
+            Kind::Tuple(ref tys) => Type::fmt_list(tys, fmt),
+            Kind::Parenthesized {
+                ref name,
+                ref args,
+                ref ret,
+            } => {
+                write!(fmt, "{}", name)?;
+                Type::fmt_list(args, fmt)?;
+
+                if let Some(ref ret) = *ret {
+                    write!(fmt, " -> ")?;
+                    ret.fmt_nested(fmt)?;
+                }
+
+                Ok(())
+            }
+            Kind::FnPtr {
+                ref qualifiers,
+                ref args,
+                ref ret,
+            } => {
+                if !qualifiers.lifetimes.is_empty() {
+                    write!(fmt, "for<{}> ", qualifiers.lifetimes.join(", "))?;
+                }
+
+                if qualifiers.r#unsafe {
+                    write!(fmt, "unsafe ")?;
+                }
+
+                match qualifiers.abi.as_deref() {
+                    Some("") => write!(fmt, "extern ")?,
+                    Some(abi) => write!(fmt, "extern \"{}\" ", abi)?,
+                    None => {}
+                }
+
+                write!(fmt, "fn")?;
+                Type::fmt_list(args, fmt)?;
+
+                if let Some(ref ret) = *ret {
+                    write!(fmt, " -> ")?;
+                    ret.fmt_nested(fmt)?;
+                }
+
+                Ok(())
+            }
+            Kind::DynTrait(ref bounds) => {
+                write!(fmt, "dyn ")?;
+                fmt_bound_rhs(bounds, fmt)
+            }
+            Kind::ImplTrait(ref bounds) => {
+                write!(fmt, "impl ")?;
+                fmt_bound_rhs(bounds, fmt)
+            }
+            Kind::Never => write!(fmt, "!"),
+            Kind::Infer => write!(fmt, "_"),
+        }
+    }
+
+    /// Formats the type in a position where a `+` would be ambiguous, e.g.
+    /// behind a reference, wrapping it in parentheses if needed.
+    fn fmt_nested(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::DynTrait(ref bounds) | Kind::ImplTrait(ref bounds) if bounds.len() > 1 => {
+                write!(fmt, "(")?;
+                self.fmt(fmt)?;
+                write!(fmt, ")")
+            }
+            _ => self.fmt(fmt),
+        }
+    }
+
+    fn fmt_list(tys: &[Type], fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "(")?;
+
+        for (i, ty) in tys.iter().enumerate() {
+            if i != 0 {
+                write!(fmt, ", ")?
+            }
+            ty.fmt(fmt)?;
+        }
+
+        write!(fmt, ")")
+    }
+}
+
+impl GenericArg {
+    /// Return a new lifetime argument, e.g. `'a`.
+    pub fn lifetime(lifetime: &str) -> Self {
+        GenericArg {
+            kind: ArgKind::Lifetime(lifetime.to_string()),
+        }
     }
 
-    fn fmt_slice(generics: &[Type], fmt: &mut Formatter<'_>) -> fmt::Result {
+    /// Return a new const argument.
+    ///
+    /// Expressions other than literals, single identifiers and blocks are
+    /// wrapped in a block, as required by Rust, e.g. `N * 2` becomes
+    /// `{ N * 2 }`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression is empty.
+    pub fn constant<T>(expr: T) -> Self
+    where
+        T: ToString,
+    {
+        let expr = expr.to_string();
+        let expr = expr.trim();
+
+        assert!(!expr.is_empty(), "const arguments cannot be empty");
+
+        let expr = if is_const_literal(expr) || is_block(expr) {
+            expr.to_string()
+        } else {
+            format!("{{ {} }}", expr)
+        };
+
+        GenericArg {
+            kind: ArgKind::Const(expr),
+        }
+    }
+
+    /// Return a new associated type binding, e.g. `Item = T`.
+    pub fn binding<T>(name: &str, ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        GenericArg {
+            kind: ArgKind::Binding(name.to_string(), ty.into()),
+        }
+    }
+
+    /// Returns the type of a type argument or associated type binding.
+    pub fn ty(&self) -> Option<&Type> {
+        match self.kind {
+            ArgKind::Type(ref ty) | ArgKind::Binding(_, ref ty) => Some(ty),
+            ArgKind::Lifetime(..) | ArgKind::Const(..) => None,
+        }
+    }
+
+    /// Returns a mutable reference to the type of a type argument or
+    /// associated type binding.
+    pub fn ty_mut(&mut self) -> Option<&mut Type> {
+        match self.kind {
+            ArgKind::Type(ref mut ty) | ArgKind::Binding(_, ref mut ty) => Some(ty),
+            ArgKind::Lifetime(..) | ArgKind::Const(..) => None,
+        }
+    }
+
+    /// Call `f` with the path of every named type within the argument.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match self.kind {
+            ArgKind::Type(ref mut ty) | ArgKind::Binding(_, ref mut ty) => ty.visit_paths(f),
+            ArgKind::Lifetime(..) | ArgKind::Const(..) => {}
+        }
+    }
+
+    /// Formats the generic argument using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ArgKind::Type(ref ty) => ty.fmt(fmt),
+            ArgKind::Lifetime(ref value) | ArgKind::Const(ref value) => write!(fmt, "{}", value),
+            ArgKind::Binding(ref name, ref ty) => {
+                write!(fmt, "{} = ", name)?;
+                ty.fmt(fmt)
+            }
+        }
+    }
+
+    fn fmt_slice(generics: &[GenericArg], fmt: &mut Formatter<'_>) -> fmt::Result {
         if !generics.is_empty() {
             write!(fmt, "<")?;
 
-            for (i, ty) in generics.iter().enumerate() {
+            for (i, arg) in generics.iter().enumerate() {
                 if i != 0 {
                     write!(fmt, ", ")?
                 }
-                ty.fmt(fmt)?;
+                arg.fmt(fmt)?;
             }
 
             write!(fmt, ">")?;
@@ -74,24 +759,74 @@ impl Type {
     }
 }
 
+/// Returns `true` if the expression is a literal or a single identifier, which
+/// may be used as a const argument without braces.
+fn is_const_literal(expr: &str) -> bool {
+    let is_quoted = |quote| expr.len() >= 2 && expr.starts_with(quote) && expr.ends_with(quote);
+
+    if is_quoted('"') || is_quoted('\'') {
+        return true;
+    }
+
+    match expr.chars().next() {
+        Some(c) if c.is_ascii_digit() => expr
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.'),
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            expr.chars().all(|c| c.is_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if the expression is a single block, e.g. `{ N * 2 }`.
+fn is_block(expr: &str) -> bool {
+    let mut depth = 0usize;
+
+    for (i, c) in expr.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return false,
+            '}' => {
+                depth -= 1;
+
+                // The first brace must be closed at the very end
+                if depth == 0 {
+                    return i == expr.len() - 1;
+                }
+            }
+            _ if i == 0 => return false,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+impl FromStr for Type {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        Parser::parse_all(src, |p| p.ty())
+    }
+}
+
 impl<'a> From<&'a str> for Type {
     fn from(src: &'a str) -> Self {
-        Type::new(src)
+        src.parse()
+            .unwrap_or_else(|_| Type::new_path(src.to_string()))
     }
 }
 
 impl From<String> for Type {
     fn from(src: String) -> Self {
-        Type {
-            name: src,
-            generics: vec![],
-        }
+        Type::from(&src[..])
     }
 }
 
 impl<'a> From<&'a String> for Type {
     fn from(src: &'a String) -> Self {
-        Type::new(src)
+        Type::from(&src[..])
     }
 }
 
@@ -100,3 +835,36 @@ impl<'a> From<&'a Type> for Type {
         src.clone()
     }
 }
+
+impl<'a> From<&'a str> for GenericArg {
+    fn from(src: &'a str) -> Self {
+        Parser::parse_all(src, |p| p.generic_arg())
+            .unwrap_or_else(|_| GenericArg::from(Type::new_path(src.to_string())))
+    }
+}
+
+impl From<String> for GenericArg {
+    fn from(src: String) -> Self {
+        GenericArg::from(&src[..])
+    }
+}
+
+impl<'a> From<&'a String> for GenericArg {
+    fn from(src: &'a String) -> Self {
+        GenericArg::from(&src[..])
+    }
+}
+
+impl From<Type> for GenericArg {
+    fn from(src: Type) -> Self {
+        GenericArg {
+            kind: ArgKind::Type(src),
+        }
+    }
+}
+
+impl<'a> From<&'a Type> for GenericArg {
+    fn from(src: &'a Type) -> Self {
+        GenericArg::from(src.clone())
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::{self, Write};
+
+use crate::bound::Bound;
+use crate::formatter::Formatter;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
+use crate::type_def::TypeDef;
+
+use crate::r#type::Type;
+
+/// Defines a type alias.
+#[derive(Debug, Clone)]
+pub struct TypeAlias {
+    type_def: TypeDef,
+
+    /// The aliased type
+    target: Type,
+}
+
+impl TypeAlias {
+    /// Return a type alias definition with the provided name and aliased type.
+    pub fn new<T>(name: &str, target: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        TypeAlias {
+            type_def: TypeDef::new(name),
+            target: target.into(),
+        }
+    }
+
+    /// Returns the type alias name.
+    pub fn name(&self) -> &str {
+        self.type_def.name()
+    }
+
+    /// Returns a reference to the type
+    pub fn ty(&self) -> &Type {
+        &self.type_def.ty
+    }
+
+    /// Returns a reference to the aliased type
+    pub fn target(&self) -> &Type {
+        &self.target
+    }
+
+    /// Set the type alias visibility.
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.type_def.vis(vis);
+        self
+    }
+
+    /// Add a generic to the type alias.
+    pub fn generic(&mut self, name: &str) -> &mut Self {
+        self.type_def.generic(name);
+        self
+    }
+
+    /// Push a generic parameter to the type alias.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        self.type_def.push_generic(param);
+        self
+    }
+
+    /// Add a `where` bound to the type alias.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
+    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        self.type_def.bound(name, ty);
+        self
+    }
+
+    /// Push a `where` predicate to the type alias.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        self.type_def.push_bound(bound);
+        self
+    }
+
+    /// Set the type alias documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.type_def.doc(docs);
+        self
+    }
+
+    /// Specify lint attribute to supress a warning or error.
+    pub fn allow(&mut self, allow: &str) -> &mut Self {
+        self.type_def.allow(allow);
+        self
+    }
+
+    /// Add an attribute to the type alias, e.g. `"cfg(unix)"`.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.type_def.attr(attribute);
+        self
+    }
+
+    /// Call `f` with the type paths used by the type alias, along with the names
+    /// of its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.type_def.visit_paths(f);
+        self.target.visit_paths(f);
+    }
+
+    /// Formats the type alias using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.type_def.fmt_signature("type", &[], fmt)?;
+
+        write!(fmt, " = ")?;
+        self.target.fmt(fmt)?;
+
+        // The `where` clause follows the aliased type
+        self.type_def.fmt_trailing_bounds(fmt)?;
+        writeln!(fmt, ";")
+    }
+}
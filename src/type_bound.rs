@@ -0,0 +1,206 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use crate::formatter::Formatter;
+use crate::parse::{ParseError, Parser};
+
+use crate::r#type::Type;
+
+/// Defines a single bound in a `+` separated list of bounds.
+///
+/// A bound is either a trait (`Clone`, `?Sized`, `for<'a> Fn(&'a str)`) or a
+/// lifetime (`'static`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeBound {
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Trait {
+        /// Lifetimes introduced by a `for<...>` binder
+        lifetimes: Vec<String>,
+
+        /// If the bound is relaxed, i.e. `?Sized`
+        maybe: bool,
+
+        ty: Type,
+    },
+    Lifetime(String),
+    /// A `+` separated list of bounds given as a single string, split into
+    /// separate bounds when pushed
+    List(Vec<TypeBound>),
+}
+
+impl TypeBound {
+    /// Return a new trait bound.
+    pub fn new<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        TypeBound {
+            kind: Kind::Trait {
+                lifetimes: vec![],
+                maybe: false,
+                ty: ty.into(),
+            },
+        }
+    }
+
+    /// Return a new relaxed trait bound, e.g. `?Sized`.
+    pub fn maybe<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        let mut bound = TypeBound::new(ty);
+
+        if let Kind::Trait { ref mut maybe, .. } = bound.kind {
+            *maybe = true;
+        }
+
+        bound
+    }
+
+    /// Return a new lifetime bound, e.g. `'static`.
+    pub fn lifetime(lifetime: &str) -> Self {
+        TypeBound {
+            kind: Kind::Lifetime(lifetime.to_string()),
+        }
+    }
+
+    /// Add a lifetime to the `for<...>` binder of a trait bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bound is not a trait bound.
+    pub fn for_lifetime(&mut self, lifetime: &str) -> &mut Self {
+        match self.kind {
+            Kind::Trait {
+                ref mut lifetimes, ..
+            } => lifetimes.push(lifetime.to_string()),
+            Kind::Lifetime(..) => panic!("lifetime bounds cannot have a `for<...>` binder"),
+            Kind::List(..) => panic!("a list of bounds cannot have a `for<...>` binder"),
+        }
+
+        self
+    }
+
+    /// Returns the bounded trait, or `None` if this is not a trait bound.
+    pub fn ty(&self) -> Option<&Type> {
+        match self.kind {
+            Kind::Trait { ref ty, .. } => Some(ty),
+            Kind::Lifetime(..) | Kind::List(..) => None,
+        }
+    }
+
+    /// Push a bound, splitting a `+` separated list given as a single string,
+    /// e.g. `Clone + Send`, into separate bounds.
+    pub(crate) fn push(bounds: &mut Vec<TypeBound>, bound: TypeBound) {
+        match bound.kind {
+            Kind::List(list) => bounds.extend(list),
+            _ => bounds.push(bound),
+        }
+    }
+
+    /// Call `f` with the path of every named type within the bound.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        match self.kind {
+            Kind::Trait { ref mut ty, .. } => ty.visit_paths(f),
+            Kind::Lifetime(..) => {}
+            Kind::List(ref mut list) => {
+                for bound in list {
+                    bound.visit_paths(f);
+                }
+            }
+        }
+    }
+
+    /// Formats the bound using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::Trait {
+                ref lifetimes,
+                maybe,
+                ref ty,
+            } => {
+                if !lifetimes.is_empty() {
+                    write!(fmt, "for<{}> ", lifetimes.join(", "))?;
+                }
+
+                if maybe {
+                    write!(fmt, "?")?;
+                }
+
+                ty.fmt(fmt)
+            }
+            Kind::Lifetime(ref lifetime) => write!(fmt, "{}", lifetime),
+            Kind::List(ref list) => {
+                for (i, bound) in list.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, " + ")?;
+                    }
+
+                    bound.fmt(fmt)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for TypeBound {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        Parser::parse_all(src, |p| p.bound())
+    }
+}
+
+impl<'a> From<&'a str> for TypeBound {
+    fn from(src: &'a str) -> Self {
+        if let Ok(bound) = src.parse() {
+            bound
+        } else if let Ok(list) = Parser::parse_all(src, |p| p.bounds()) {
+            TypeBound {
+                kind: Kind::List(list),
+            }
+        } else if src.starts_with('\'') {
+            TypeBound::lifetime(src)
+        } else if let Some(rest) = src.strip_prefix('?') {
+            TypeBound::maybe(rest)
+        } else {
+            TypeBound::new(src)
+        }
+    }
+}
+
+impl From<String> for TypeBound {
+    fn from(src: String) -> Self {
+        TypeBound::from(&src[..])
+    }
+}
+
+impl<'a> From<&'a String> for TypeBound {
+    fn from(src: &'a String) -> Self {
+        TypeBound::from(&src[..])
+    }
+}
+
+impl From<Type> for TypeBound {
+    fn from(src: Type) -> Self {
+        TypeBound::new(src)
+    }
+}
+
+impl<'a> From<&'a Type> for TypeBound {
+    fn from(src: &'a Type) -> Self {
+        TypeBound::new(src.clone())
+    }
+}
+
+impl<'a> From<&'a TypeBound> for TypeBound {
+    fn from(src: &'a TypeBound) -> Self {
+        src.clone()
+    }
+}
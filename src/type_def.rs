@@ -2,7 +2,9 @@ use std::fmt::{self, Write};
 
 use crate::bound::Bound;
 use crate::docs::Docs;
-use crate::formatter::{fmt_bounds, Formatter};
+use crate::formatter::{fmt_bound_rhs, fmt_bounds, fmt_generics, Formatter};
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
 
 use crate::r#type::Type;
 
@@ -10,12 +12,15 @@ use crate::r#type::Type;
 #[derive(Debug, Clone)]
 pub struct TypeDef {
     pub ty: Type,
+    name: String,
+    generics: Vec<GenericParam>,
     vis: Option<String>,
     docs: Option<Docs>,
     derive: Vec<String>,
     allow: Vec<String>,
     repr: Option<String>,
     bounds: Vec<Bound>,
+    attributes: Vec<String>,
     macros: Vec<String>,
 }
 
@@ -24,28 +29,59 @@ impl TypeDef {
     pub fn new(name: &str) -> Self {
         TypeDef {
             ty: Type::new(name),
+            name: name.to_string(),
+            generics: vec![],
             vis: None,
             docs: None,
             derive: vec![],
             allow: vec![],
             repr: None,
             bounds: vec![],
+            attributes: vec![],
             macros: vec![],
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn vis(&mut self, vis: &str) {
         self.vis = Some(vis.to_string());
     }
 
+    pub fn generic(&mut self, name: &str) {
+        for param in GenericParam::parse_list(name) {
+            self.push_generic(param);
+        }
+    }
+
+    pub fn push_generic(&mut self, param: GenericParam) {
+        GenericParam::push(&mut self.generics, param);
+
+        // Keep the type in sync with the declared parameters
+        self.ty = Type::new(&self.name);
+
+        for param in &self.generics {
+            self.ty.generic(param.to_arg());
+        }
+    }
+
     pub fn bound<T>(&mut self, name: &str, ty: T)
     where
-        T: Into<Type>,
+        T: Into<TypeBound>,
     {
-        self.bounds.push(Bound {
-            name: name.to_string(),
-            bound: vec![ty.into()],
-        });
+        let mut bound = Bound::parse_lhs(name);
+        bound.bound(ty);
+        self.push_bound(bound);
+    }
+
+    pub fn push_bound(&mut self, bound: Bound) {
+        Bound::push(&mut self.bounds, bound);
+    }
+
+    pub fn attr(&mut self, attribute: &str) {
+        self.attributes.push(attribute.to_string());
     }
 
     pub fn r#macro(&mut self, r#macro: &str) {
@@ -68,10 +104,34 @@ impl TypeDef {
         self.repr = Some(repr.to_string());
     }
 
+    /// Call `f` with the type paths used by the generics and `where` clause,
+    /// along with the names of the generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for param in &mut self.generics {
+            param.visit_paths(f);
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_paths(f);
+        }
+    }
+
     pub fn fmt_head(
         &self,
         keyword: &str,
-        parents: &[Type],
+        parents: &[TypeBound],
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        self.fmt_signature(keyword, parents, fmt)?;
+        fmt_bounds(&self.bounds, fmt)
+    }
+
+    /// Formats everything up to the `where` clause, e.g. for type aliases
+    /// whose `where` clause follows the aliased type.
+    pub(crate) fn fmt_signature(
+        &self,
+        keyword: &str,
+        parents: &[TypeBound],
         fmt: &mut Formatter<'_>,
     ) -> fmt::Result {
         if let Some(ref docs) = self.docs {
@@ -81,35 +141,43 @@ impl TypeDef {
         self.fmt_allow(fmt)?;
         self.fmt_derive(fmt)?;
         self.fmt_repr(fmt)?;
+        self.fmt_attributes(fmt)?;
         self.fmt_macros(fmt)?;
 
         if let Some(ref vis) = self.vis {
             write!(fmt, "{} ", vis)?;
         }
 
-        write!(fmt, "{} ", keyword)?;
-        self.ty.fmt(fmt)?;
+        write!(fmt, "{} {}", keyword, self.name)?;
+        fmt_generics(&self.generics, fmt)?;
 
         if !parents.is_empty() {
-            for (i, ty) in parents.iter().enumerate() {
-                if i == 0 {
-                    write!(fmt, ": ")?;
-                } else {
-                    write!(fmt, " + ")?;
-                }
+            write!(fmt, ": ")?;
+            fmt_bound_rhs(parents, fmt)?;
+        }
 
-                ty.fmt(fmt)?;
+        Ok(())
+    }
+
+    /// Formats the `where` clause on a single line per predicate, ending
+    /// with the last predicate.
+    pub(crate) fn fmt_trailing_bounds(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for (i, bound) in self.bounds.iter().enumerate() {
+            if i == 0 {
+                write!(fmt, "\nwhere ")?;
+            } else {
+                write!(fmt, ",\n      ")?;
             }
-        }
 
-        fmt_bounds(&self.bounds, fmt)?;
+            bound.fmt(fmt)?;
+        }
 
         Ok(())
     }
 
-    fn fmt_allow(&self, fmt: &mut Formatter) -> fmt::Result {
+    fn fmt_allow(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for allow in &self.allow {
-            write!(fmt, "#[allow({})]\n", allow)?;
+            writeln!(fmt, "#[allow({})]", allow)?;
         }
 
         Ok(())
@@ -117,7 +185,7 @@ impl TypeDef {
 
     fn fmt_repr(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if let Some(ref repr) = self.repr {
-            write!(fmt, "#[repr({})]\n", repr)?;
+            writeln!(fmt, "#[repr({})]", repr)?;
         }
 
         Ok(())
@@ -134,7 +202,15 @@ impl TypeDef {
                 write!(fmt, "{}", name)?;
             }
 
-            write!(fmt, ")]\n")?;
+            writeln!(fmt, ")]")?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_attributes(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for attr in &self.attributes {
+            writeln!(fmt, "#[{}]", attr)?;
         }
 
         Ok(())
@@ -142,7 +218,7 @@ impl TypeDef {
 
     fn fmt_macros(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for m in self.macros.iter() {
-            write!(fmt, "{}\n", m)?;
+            writeln!(fmt, "{}", m)?;
         }
         Ok(())
     }
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::bound::Bound;
+use crate::field::Field;
+use crate::fields::Fields;
+use crate::formatter::Formatter;
+use crate::generic_param::GenericParam;
+use crate::type_bound::TypeBound;
+use crate::type_def::TypeDef;
+
+use crate::r#type::Type;
+
+/// Defines a union.
+#[derive(Debug, Clone)]
+pub struct Union {
+    type_def: TypeDef,
+
+    /// Union fields
+    fields: Fields,
+}
+
+impl Union {
+    /// Return a union definition with the provided name
+    pub fn new(name: &str) -> Self {
+        Union {
+            type_def: TypeDef::new(name),
+            fields: Fields::Empty,
+        }
+    }
+
+    /// Returns the union name.
+    pub fn name(&self) -> &str {
+        self.type_def.name()
+    }
+
+    /// Returns a reference to the type
+    pub fn ty(&self) -> &Type {
+        &self.type_def.ty
+    }
+
+    /// Set the union visibility.
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.type_def.vis(vis);
+        self
+    }
+
+    /// Add a generic to the union.
+    pub fn generic(&mut self, name: &str) -> &mut Self {
+        self.type_def.generic(name);
+        self
+    }
+
+    /// Push a generic parameter to the union.
+    pub fn push_generic(&mut self, param: GenericParam) -> &mut Self {
+        self.type_def.push_generic(param);
+        self
+    }
+
+    /// Add a `where` bound to the union.
+    ///
+    /// Bounds on the same type are merged into a single predicate.
+    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<TypeBound>,
+    {
+        self.type_def.bound(name, ty);
+        self
+    }
+
+    /// Push a `where` predicate to the union.
+    pub fn push_bound(&mut self, bound: Bound) -> &mut Self {
+        self.type_def.push_bound(bound);
+        self
+    }
+
+    /// Set the union documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.type_def.doc(docs);
+        self
+    }
+
+    /// Add a new type that the union should derive.
+    pub fn derive(&mut self, name: &str) -> &mut Self {
+        self.type_def.derive(name);
+        self
+    }
+
+    /// Specify lint attribute to supress a warning or error.
+    pub fn allow(&mut self, allow: &str) -> &mut Self {
+        self.type_def.allow(allow);
+        self
+    }
+
+    /// Specify representation.
+    pub fn repr(&mut self, repr: &str) -> &mut Self {
+        self.type_def.repr(repr);
+        self
+    }
+
+    /// Push a named field to the union.
+    pub fn push_field(&mut self, field: Field) -> &mut Self {
+        self.fields.push_named(field);
+        self
+    }
+
+    /// Add a named field to the union.
+    pub fn field<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.fields.named(name, ty);
+        self
+    }
+
+    /// Call `f` with the type paths used by the union, along with the names
+    /// of its generic parameters.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.type_def.visit_paths(f);
+        self.fields.visit_paths(f);
+    }
+
+    /// Formats the union using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Fields::Empty = self.fields {
+            panic!("unions must have at least one field");
+        }
+
+        self.type_def.fmt_head("union", &[], fmt)?;
+        self.fields.fmt(fmt)
+    }
+}
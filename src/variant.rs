@@ -1,5 +1,7 @@
 use std::fmt::{self, Write};
 
+use crate::docs::Docs;
+use crate::field::Field;
 use crate::fields::Fields;
 use crate::formatter::Formatter;
 
@@ -10,6 +12,15 @@ use crate::r#type::Type;
 pub struct Variant {
     name: String,
     fields: Fields,
+
+    /// Explicit discriminant expression
+    discriminant: Option<String>,
+
+    /// Variant documentation
+    docs: Option<Docs>,
+
+    /// Variant attributes, e.g., `#[default]`.
+    attributes: Vec<String>,
 }
 
 impl Variant {
@@ -18,6 +29,9 @@ impl Variant {
         Variant {
             name: name.to_string(),
             fields: Fields::Empty,
+            discriminant: None,
+            docs: None,
+            attributes: vec![],
         }
     }
 
@@ -30,17 +44,65 @@ impl Variant {
         self
     }
 
+    /// Push a named field to the variant.
+    pub fn push_named(&mut self, field: Field) -> &mut Self {
+        self.fields.push_named(field);
+        self
+    }
+
     /// Add a tuple field to the variant.
-    pub fn tuple(&mut self, ty: &str) -> &mut Self {
+    pub fn tuple<T>(&mut self, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
         self.fields.tuple(ty);
         self
     }
 
+    /// Set the explicit discriminant of the variant, e.g. `3` in `Foo = 3`.
+    pub fn discriminant<T>(&mut self, expr: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.discriminant = Some(expr.to_string());
+        self
+    }
+
+    /// Set the variant documentation.
+    pub fn doc(&mut self, docs: &str) -> &mut Self {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an attribute to the variant.
+    pub fn attr(&mut self, attribute: &str) -> &mut Self {
+        self.attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Call `f` with the type paths used by the variant fields.
+    pub(crate) fn visit_paths(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.fields.visit_paths(f);
+    }
+
     /// Formats the variant using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
         write!(fmt, "{}", self.name)?;
-        self.fields.fmt(fmt)?;
-        write!(fmt, ",\n")?;
+        self.fields.fmt_variant(fmt)?;
+
+        if let Some(ref discriminant) = self.discriminant {
+            write!(fmt, " = {}", discriminant)?;
+        }
+
+        writeln!(fmt, ",")?;
 
         Ok(())
     }
@@ -604,4 +604,1655 @@ enum IpAddrKind {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn fn_with_structured_types() {
+    let mut scope = Scope::new();
+
+    let mut buf = Type::mut_reference(Type::array("u8", 16));
+    buf.lifetime("'a");
+
+    let mut iter = Type::new("Iterator");
+    iter.binding("Item", Type::tuple(vec!["A", "B"]));
+
+    let mut callback = Type::fn_ptr(vec!["u32"]);
+    callback.ret("bool");
+
+    scope
+        .new_fn("structured")
+        .arg("buf", buf)
+        .arg("bytes", Type::reference(Type::slice("u8")))
+        .arg("single", Type::tuple(vec!["u8"]))
+        .arg("callback", callback)
+        .arg(
+            "handler",
+            Type::reference(Type::dyn_trait(vec!["Fn()", "Send"])),
+        )
+        .arg("ptr", Type::mut_ptr(Type::unit()))
+        .ret(Type::impl_trait(vec![iter]));
+
+    let expect = r#"
+fn structured(buf: &'a mut [u8; 16], bytes: &[u8], single: (u8,), callback: fn(u32) -> bool, handler: &(dyn Fn() + Send), ptr: *mut ()) -> impl Iterator<Item = (A, B)> {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_type_bounds() {
+    let mut scope = Scope::new();
+
+    let mut handler = Type::new("Box");
+    handler.generic(Type::dyn_trait(vec![
+        TypeBound::new("Error"),
+        TypeBound::new("Send"),
+        TypeBound::lifetime("'static"),
+    ]));
+
+    scope
+        .new_struct("Foo")
+        .field("never", Type::never())
+        .field("handler", handler);
+
+    let expect = r#"
+struct Foo {
+    never: !,
+    handler: Box<dyn Error + Send + 'static>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn tuple_struct_with_bounds() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .generic("T")
+        .generic("U")
+        .bound("T", "Send")
+        .bound("U", "Sync")
+        .tuple_field("T")
+        .tuple_field("U");
+
+    scope.new_struct("Bar").generic("T").bound("T", "Default");
+
+    let expect = r#"
+struct Foo<T, U>(T, U)
+where T: Send,
+      U: Sync;
+
+struct Bar<T>
+where T: Default;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn parse_types() {
+    let cases = [
+        "HashMap<String, Vec<Option<u8>>>",
+        "&'a mut [u8; 16]",
+        "(A, B)",
+        "(A,)",
+        "()",
+        "fn(u32) -> bool",
+        "unsafe fn()",
+        "extern \"C\" fn(u8)",
+        "extern fn()",
+        "for<'a> fn(&'a u8)",
+        "for<'a, 'b> unsafe extern \"C\" fn(&'a u8, &'b u8) -> &'a u8",
+        "Box<dyn Fn(&str) -> bool + Send + 'static>",
+        "&(dyn Any + Send)",
+        "impl Iterator<Item = T>",
+        "*const std::ffi::c_void",
+        "Cow<'static, str>",
+        "!",
+        "_",
+        "Vec::<u8>",
+        "<T as Iterator>::Item",
+        "Box<<T as IntoIterator>::IntoIter>",
+    ];
+
+    for src in &cases {
+        let ty: Type = src.parse().unwrap();
+
+        let mut dst = String::new();
+        ty.fmt(&mut Formatter::new(&mut dst)).unwrap();
+
+        assert_eq!(dst, *src);
+    }
+
+    assert!("HashMap<String".parse::<Type>().is_err());
+    assert!("&' a str".parse::<Type>().is_err());
+
+    let mut ty = Type::new("<T as Iterator>::Item");
+    ty.generic("U");
+
+    let mut dst = String::new();
+    ty.fmt(&mut Formatter::new(&mut dst)).unwrap();
+
+    assert_eq!(dst, "<T as Iterator>::Item<U>");
+}
+
+#[test]
+fn parsed_type_generics() {
+    let mut scope = Scope::new();
+
+    let mut map = Type::from("std::collections::HashMap<String>");
+    map.generic("Vec<Option<u8>>");
+
+    assert_eq!(map.name(), Some("std::collections::HashMap"));
+    assert_eq!(map.generics()[0].ty(), Some(&Type::new("String")));
+
+    scope
+        .new_struct("Foo")
+        .generic("'a")
+        .field("map", map)
+        .field("bytes", Type::new("Cow<[u8]>").path("std::borrow"))
+        .field("raw", "<T as Iterator>::Item");
+
+    let expect = r#"
+struct Foo<'a> {
+    map: std::collections::HashMap<String, Vec<Option<u8>>>,
+    bytes: std::borrow::Cow<[u8]>,
+    raw: <T as Iterator>::Item,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_lifetime_generics() {
+    let mut scope = Scope::new();
+
+    let mut default = GenericParam::new("S");
+    default.bound("Clone").default("String");
+
+    let mut lifetime = GenericParam::lifetime("'b");
+    lifetime.bound("'a");
+
+    let s = scope
+        .new_struct("Foo")
+        .push_generic(default)
+        .generic("T: 'a")
+        .generic("'a")
+        .push_generic(lifetime)
+        .field("one", "&'a T")
+        .field("two", "&'b S");
+
+    assert_eq!(s.ty(), &Type::new("Foo<'a, 'b, T, S>"));
+
+    let expect = r#"
+struct Foo<'a, 'b: 'a, T: 'a, S: Clone = String> {
+    one: &'a T,
+    two: &'b S,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_and_fn_with_lifetime_generics() {
+    let mut scope = Scope::new();
+
+    let imp = scope.new_impl("Foo<'a, T>");
+    imp.generic("T: ?Sized").generic("'a");
+    imp.new_fn("get")
+        .generic("'b, U: From<&'b T>")
+        .arg("t", "&'b T")
+        .ret("U")
+        .line("U::from(t)");
+
+    let expect = r#"
+impl<'a, T: ?Sized> Foo<'a, T> {
+    fn get<'b, U: From<&'b T>>(t: &'b T) -> U {
+        U::from(t)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn const_generics() {
+    let mut scope = Scope::new();
+
+    let mut len = GenericParam::constant("N", "usize");
+    len.default(GenericArg::constant(4));
+
+    let mut doubled = Type::new("Buf");
+    doubled.const_generic("N * 2");
+
+    scope
+        .new_struct("Buf")
+        .push_generic(len)
+        .generic("T")
+        .field("data", "[T; N]");
+
+    scope
+        .new_impl("[u8; N]")
+        .generic("const N: usize")
+        .impl_trait("Foo<{ N }, true>")
+        .new_fn("double")
+        .ret(doubled)
+        .line("todo!()");
+
+    let expect = r#"
+struct Buf<T, const N: usize = 4> {
+    data: [T; N],
+}
+
+impl<const N: usize> Foo<{ N }, true> for [u8; N] {
+    fn double() -> Buf<{ N * 2 }> {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    let mut ty = Type::new("Foo");
+    ty.const_generic(-1)
+        .const_generic("a.b")
+        .const_generic("N::MAX")
+        .const_generic("{ A } + { B }")
+        .const_generic("'x'")
+        .const_generic("1_000u32");
+
+    let mut dst = String::new();
+    ty.fmt(&mut Formatter::new(&mut dst)).unwrap();
+
+    assert_eq!(
+        dst,
+        "Foo<{ -1 }, { a.b }, { N::MAX }, { { A } + { B } }, 'x', 1_000u32>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "const arguments cannot be empty")]
+fn empty_const_generic() {
+    GenericArg::constant("");
+}
+
+#[test]
+fn fn_with_multi_bound_where_clause() {
+    let mut scope = Scope::new();
+
+    let mut predicate = Bound::new("Vec<T>");
+    predicate.bound("Debug");
+
+    scope
+        .new_fn("call")
+        .generic("'b, T, F")
+        .arg("f", "F")
+        .bound("T", "Clone")
+        .bound("T", "Send")
+        .bound("T", TypeBound::lifetime("'static"))
+        .bound("for<'a> F", "Fn(&'a str) -> T")
+        .bound("'b", "'static")
+        .push_bound(predicate)
+        .push_bound("U: ?Sized".parse().unwrap())
+        .line("f(\"hello\")");
+
+    let expect = r#"
+fn call<'b, T, F>(f: F)
+where T: Clone + Send + 'static,
+      for<'a> F: Fn(&'a str) -> T,
+      'b: 'static,
+      Vec<T>: Debug,
+      U: ?Sized,
+{
+    f("hello")
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn bound_lists_are_split() {
+    let mut bound = Bound::new("T");
+    bound.bound("Clone + Send").bound("'static + ?Sized");
+
+    assert_eq!(
+        bound,
+        "T: Clone + Send + 'static + ?Sized"
+            .parse::<Bound>()
+            .unwrap()
+    );
+
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("call")
+        .generic("T")
+        .bound("T", "Clone + Send")
+        .bound("T", "Sync");
+
+    let expect = r#"
+fn call<T>()
+where T: Clone + Send + Sync,
+{
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn type_alias() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_type_alias("Result", "std::result::Result<T, Error>")
+        .vis("pub")
+        .doc("A specialized result type.")
+        .generic("T");
+
+    scope
+        .new_module("foo")
+        .new_type_alias("Callback", "Box<dyn Fn(T) + Send>")
+        .attr("cfg(unix)")
+        .generic("T")
+        .bound("T", "Debug");
+
+    let expect = r#"
+/// A specialized result type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+mod foo {
+    #[cfg(unix)]
+    type Callback<T> = Box<dyn Fn(T) + Send>
+    where T: Debug;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn const_and_static_items() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_const("TABLE", "[u8; 4]")
+        .vis("pub")
+        .doc("Lookup table.")
+        .value("[1, 2, 3, 4]");
+
+    scope
+        .new_static("COUNTER", "AtomicUsize")
+        .attr("no_mangle")
+        .value("AtomicUsize::new(0)");
+
+    scope
+        .new_static("BUF", "[u8; 16]")
+        .set_mut(true)
+        .value("[0; 16]");
+
+    let trt = scope.new_trait("Limits");
+    trt.new_const("MIN", "u32");
+    trt.new_const("MAX", "u32").value("u32::MAX");
+    trt.new_fn("check").arg_ref_self();
+
+    scope
+        .new_impl("Foo")
+        .impl_trait("Limits")
+        .new_const("MIN", "u32")
+        .value(1);
+
+    let expect = r#"
+/// Lookup table.
+pub const TABLE: [u8; 4] = [1, 2, 3, 4];
+
+#[no_mangle]
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+static mut BUF: [u8; 16] = [0; 16];
+
+trait Limits {
+    const MIN: u32;
+    const MAX: u32 = u32::MAX;
+
+    fn check(&self);
+}
+
+impl Limits for Foo {
+    const MIN: u32 = 1;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn union_with_repr() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_module("ffi")
+        .new_union("Value")
+        .vis("pub")
+        .repr("C")
+        .derive("Clone")
+        .derive("Copy")
+        .field("int", "i64")
+        .field("ptr", "*mut std::ffi::c_void");
+
+    let expect = r#"
+mod ffi {
+    #[derive(Clone, Copy)]
+    #[repr(C)]
+    pub union Value {
+        int: i64,
+        ptr: *mut std::ffi::c_void,
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn macro_rules_in_mod() {
+    let mut scope = Scope::new();
+
+    let module = scope.new_module("macros");
+    module.set_macro_use(true);
+
+    let mac = module.new_macro_rules("square");
+    mac.doc("Squares an expression.").set_export(true);
+    mac.new_rule("$e:expr").line("$e * $e");
+
+    let mut block = Block::new("$(");
+    block.line("$crate::square!($e);").after(")*");
+    mac.new_rule("($($e:expr),*)").push_block(block);
+
+    let expect = r#"
+#[macro_use]
+mod macros {
+    /// Squares an expression.
+    #[macro_export]
+    macro_rules! square {
+        ($e:expr) => {
+            $e * $e
+        };
+        ($($e:expr),*) => {
+            $( {
+                $crate::square!($e);
+            })*
+        };
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn macro_rules_matcher_delimiters() {
+    let mut scope = Scope::new();
+
+    let mac = scope.new_macro_rules("add");
+    mac.new_rule("($a:expr) + ($b:expr)").line("$a + $b");
+    mac.new_rule("[$($e:expr),*]").line("0 $(+ $e)*");
+
+    let expect = r#"
+macro_rules! add {
+    (($a:expr) + ($b:expr)) => {
+        $a + $b
+    };
+    [$($e:expr),*] => {
+        0 $(+ $e)*
+    };
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn extern_block_with_foreign_items() {
+    let mut scope = Scope::new();
+
+    let block = scope.new_extern_block("C");
+    block.link("foo").set_unsafe(true);
+
+    block.new_fn("foo").vis("pub").arg("x", "i32").ret("i32");
+
+    block
+        .new_fn("printf")
+        .arg("fmt", Type::ptr("c_char"))
+        .set_variadic(true)
+        .ret("c_int");
+
+    block.new_static("BAR", "u8").set_mut(true);
+    block
+        .new_fn("abs")
+        .arg("x", "i32")
+        .ret("i32")
+        .set_safe(true);
+    block
+        .new_fn("free")
+        .arg("ptr", Type::mut_ptr("c_void"))
+        .set_unsafe(true);
+    block.new_static("ERRNO", "c_int").vis("pub").set_safe(true);
+
+    let expect = r#"
+#[link(name = "foo")]
+unsafe extern "C" {
+    pub fn foo(x: i32) -> i32;
+    fn printf(fmt: *const c_char, ...) -> c_int;
+    static mut BAR: u8;
+    safe fn abs(x: i32) -> i32;
+    unsafe fn free(ptr: *mut c_void);
+    pub safe static ERRNO: c_int;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_with_provided_fns() {
+    let mut scope = Scope::new();
+
+    let t = scope.new_trait("Shape");
+    t.new_fn("area").arg_ref_self().ret("f64");
+
+    t.new_provided_fn("is_empty")
+        .arg_ref_self()
+        .ret("bool")
+        .line("self.area() == 0.0");
+
+    t.new_provided_fn("boxed")
+        .arg_self()
+        .ret("Box<Self>")
+        .self_sized()
+        .line("Box::new(self)");
+
+    let expect = r#"
+trait Shape {
+    fn area(&self) -> f64;
+
+    fn is_empty(&self) -> bool {
+        self.area() == 0.0
+    }
+
+    fn boxed(self) -> Box<Self>
+    where Self: Sized,
+    {
+        Box::new(self)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn required_fn_with_bounds() {
+    let mut scope = Scope::new();
+
+    let t = scope.new_trait("Shape");
+    t.new_fn("new").ret("Self").self_sized();
+    t.new_fn("scale")
+        .generic("T")
+        .arg_self()
+        .arg("factor", "T")
+        .ret("Self")
+        .self_sized()
+        .bound("T", "Into<f64>");
+
+    let expect = r#"
+trait Shape {
+    fn new() -> Self
+    where
+        Self: Sized;
+
+    fn scale<T>(self, factor: T) -> Self
+    where
+        Self: Sized,
+        T: Into<f64>;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn generic_associated_types() {
+    let mut scope = Scope::new();
+
+    let t = scope.new_trait("LendingIterator");
+    t.associated_type("Item")
+        .doc("The yielded item.")
+        .generic("'a")
+        .bound("Debug")
+        .where_bound("Self", "'a");
+    t.associated_type("Error").ty("std::io::Error");
+
+    let imp = scope.new_impl("Windows");
+    imp.generic("T")
+        .target_generic("T")
+        .impl_trait("LendingIterator");
+    let mut item = Type::reference(Type::slice("T"));
+    item.lifetime("'a");
+
+    imp.new_associated_type("Item", item)
+        .generic("'a")
+        .where_bound("Self", "'a")
+        .attr("allow(unused)");
+
+    let expect = r#"
+trait LendingIterator {
+    /// The yielded item.
+    type Item<'a>: Debug where Self: 'a;
+    type Error = std::io::Error;
+}
+
+impl<T> LendingIterator for Windows<T> {
+    #[allow(unused)]
+    type Item<'a> = &'a [T] where Self: 'a;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_variants_with_discriminants_and_docs() {
+    let mut scope = Scope::new();
+
+    let e = scope.new_enum("Opcode");
+    e.repr("u8").derive("Default");
+
+    e.new_variant("Nop")
+        .doc("Does nothing.")
+        .attr("default")
+        .discriminant(0);
+    e.new_variant("Jump")
+        .attr("cfg(feature = \"jump\")")
+        .discriminant("1 << 4");
+
+    let mut offset = Field::new("offset", "u32");
+    offset
+        .doc(vec!["Byte offset."])
+        .annotation(vec!["#[serde(rename = \"off\")]"]);
+
+    e.new_variant("Load").push_named(offset).named("len", "u16");
+    let mut bytes = Type::reference(Type::slice("u8"));
+    bytes.lifetime("'static");
+
+    e.new_variant("Store").tuple(bytes).tuple(Type::unit());
+
+    let expect = r#"
+#[derive(Default)]
+#[repr(u8)]
+enum Opcode {
+    /// Does nothing.
+    #[default]
+    Nop = 0,
+    #[cfg(feature = "jump")]
+    Jump = 1 << 4,
+    Load {
+        /// Byte offset.
+        #[serde(rename = "off")]
+        offset: u32,
+        len: u16,
+    },
+    Store(&'static [u8], ()),
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn fn_qualifiers_and_receivers() {
+    let mut scope = Scope::new();
+
+    let imp = scope.new_impl("Buf");
+
+    imp.new_fn("len")
+        .vis("pub")
+        .set_const(true)
+        .set_unsafe(true)
+        .extern_abi("C")
+        .receiver(Receiver::reference().lifetime("'a").clone())
+        .ret("usize")
+        .line("self.len");
+
+    imp.new_fn("poll")
+        .set_async(true)
+        .set_unsafe(true)
+        .receiver(Receiver::typed("Pin<&mut Self>"))
+        .line("todo!()");
+
+    let mut boxed = Receiver::typed("Box<Self>");
+    boxed.set_mut(true);
+
+    imp.new_fn("into_inner")
+        .receiver(boxed)
+        .arg("x", "u8")
+        .line("todo!()");
+
+    imp.new_fn("consume")
+        .receiver(Receiver::mut_value())
+        .line("todo!()");
+
+    let expect = r#"
+impl Buf {
+    pub const unsafe extern "C" fn len(&'a self) -> usize {
+        self.len
+    }
+
+    async unsafe fn poll(self: Pin<&mut Self>) {
+        todo!()
+    }
+
+    fn into_inner(mut self: Box<Self>, x: u8) {
+        todo!()
+    }
+
+    fn consume(mut self) {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn fn_with_pattern_args() {
+    let mut scope = Scope::new();
+
+    let f = scope.new_fn("handle");
+    f.new_arg("buf", Type::mut_reference(Type::slice("u8")))
+        .set_mut(true);
+    f.new_arg("(a, b)", Type::tuple(vec!["u8", "u8"]));
+    f.new_arg("Point { x, y }", "Point")
+        .attr("cfg(feature = \"points\")");
+    f.new_arg("_", "Context").attr("allow(unused)");
+    f.arg("n", "usize");
+
+    let expect = r#"
+fn handle(mut buf: &mut [u8], (a, b): (u8, u8), #[cfg(feature = "points")] Point { x, y }: Point, #[allow(unused)] _: Context, n: usize) {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "only identifier patterns can be `mut`")]
+fn mut_destructuring_arg() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("handle")
+        .new_arg("(a, b)", Type::tuple(vec!["u8", "u8"]))
+        .set_mut(true);
+
+    scope.to_string();
+}
+
+#[test]
+fn unsafe_and_negative_impls() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_impl("Handle")
+        .impl_trait("Send")
+        .set_unsafe(true)
+        .doc("Handles are only ever accessed behind a lock.")
+        .attr("cfg(feature = \"sync\")");
+
+    scope
+        .new_impl("Handle")
+        .impl_trait("Sync")
+        .set_negative(true);
+
+    scope
+        .new_impl("Handle")
+        .impl_trait("Clone")
+        .attr("automatically_derived")
+        .new_fn("clone")
+        .arg_ref_self()
+        .ret("Self")
+        .line("Handle(self.0)");
+
+    let expect = r#"
+/// Handles are only ever accessed behind a lock.
+#[cfg(feature = "sync")]
+unsafe impl Send for Handle {
+}
+
+impl !Sync for Handle {
+}
+
+#[automatically_derived]
+impl Clone for Handle {
+    fn clone(&self) -> Self {
+        Handle(self.0)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn unsafe_trait_with_attrs_and_parents() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_trait("Zeroable")
+        .vis("pub")
+        .set_unsafe(true)
+        .attr("must_use")
+        .attr("cfg(feature = \"zeroable\")")
+        .generic("'a")
+        .parent("Copy")
+        .parent(TypeBound::maybe("Sized"))
+        .parent("'a")
+        .parent("for<'b> From<&'b [u8]>");
+
+    scope.new_trait("Marker").set_auto(true).set_unsafe(true);
+
+    let expect = r#"
+#[must_use]
+#[cfg(feature = "zeroable")]
+pub unsafe trait Zeroable<'a>: Copy + ?Sized + 'a + for<'b> From<&'b [u8]> {
+}
+
+unsafe auto trait Marker {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn module_docs_and_attrs() {
+    let mut scope = Scope::new();
+
+    let tests = scope.new_module("tests");
+    tests
+        .doc("Generated tests.")
+        .attr("cfg(test)")
+        .inner_attr("allow(dead_code)")
+        .import("super", "*");
+    tests.new_fn("it_works").attr("test");
+
+    let proto = scope.new_module("proto");
+    proto
+        .vis("pub")
+        .attr("path = \"gen/proto.rs\"")
+        .doc("Protocol types.")
+        .set_inline(false);
+    proto.new_struct("Frame");
+
+    let expect = r#"
+#[cfg(test)]
+mod tests {
+    //! Generated tests.
+    #![allow(dead_code)]
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+    }
+}
+
+#[path = "gen/proto.rs"]
+pub mod proto;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    let expect = r#"
+//! Protocol types.
+
+struct Frame;"#;
+
+    assert_eq!(
+        scope.get_module_mut("proto").unwrap().scope().to_string(),
+        &expect[1..]
+    );
+}
+
+#[test]
+fn scope_docs_and_inner_attrs() {
+    let mut scope = Scope::new();
+
+    scope
+        .doc("Generated bindings.\n\nDo not edit.")
+        .inner_attr("no_std")
+        .inner_attr("allow(clippy::all)")
+        .inner_attr("cfg_attr(docsrs, feature(doc_cfg))");
+
+    scope.import("core::fmt", "Debug");
+    scope.new_struct("Foo");
+
+    let expect = r#"
+//! Generated bindings.
+//!
+//! Do not edit.
+#![no_std]
+#![allow(clippy::all)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+use core::fmt::Debug;
+
+struct Foo;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_to_files() {
+    use std::path::PathBuf;
+
+    let mut scope = Scope::new();
+
+    let api = scope.new_module("api");
+    api.vis("pub").set_inline(false);
+    api.new_struct("Client");
+
+    let v1 = api.new_module("v1");
+    v1.vis("pub").set_inline(false).set_mod_rs(true);
+    v1.new_struct("Request");
+
+    let util = scope.new_module("util");
+    util.new_module("helpers")
+        .set_inline(false)
+        .new_fn("helper");
+
+    let files = scope.to_files("lib.rs");
+
+    let expect = vec![
+        (
+            PathBuf::from("lib.rs"),
+            "pub mod api;\n\nmod util {\n    mod helpers;\n}\n",
+        ),
+        (PathBuf::from("api.rs"), "struct Client;\n\npub mod v1;\n"),
+        (PathBuf::from("api/v1/mod.rs"), "struct Request;\n"),
+        (PathBuf::from("util/helpers.rs"), "fn helper() {\n}\n"),
+    ];
+
+    let files: Vec<_> = files.iter().map(|(p, s)| (p.clone(), &s[..])).collect();
+    assert_eq!(files, expect);
+
+    let dir = std::env::temp_dir().join(format!("codegen-scope-to-files-{}", std::process::id()));
+    scope.write_to_dir(&dir, "lib.rs").unwrap();
+
+    for (path, contents) in expect {
+        assert_eq!(std::fs::read_to_string(dir.join(path)).unwrap(), contents);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn nested_file_modules_layout() {
+    use std::path::PathBuf;
+
+    let mut scope = Scope::new();
+
+    let tcp = scope.new_module("tcp");
+    tcp.set_inline(false);
+
+    let stream = tcp.new_module("stream");
+    stream.set_inline(false);
+    stream.new_module("buf").set_inline(false).set_mod_rs(true);
+
+    let udp = scope.new_module("udp");
+    udp.set_inline(false).set_mod_rs(true);
+    udp.new_module("socket").set_inline(false);
+
+    let paths =
+        |root: &str| -> Vec<PathBuf> { scope.to_files(root).into_iter().map(|(p, _)| p).collect() };
+
+    assert_eq!(
+        paths("src/net.rs"),
+        vec![
+            PathBuf::from("src/net.rs"),
+            PathBuf::from("src/net/tcp.rs"),
+            PathBuf::from("src/net/tcp/stream.rs"),
+            PathBuf::from("src/net/tcp/stream/buf/mod.rs"),
+            PathBuf::from("src/net/udp/mod.rs"),
+            PathBuf::from("src/net/udp/socket.rs"),
+        ]
+    );
+
+    assert_eq!(
+        paths("main.rs"),
+        vec![
+            PathBuf::from("main.rs"),
+            PathBuf::from("tcp.rs"),
+            PathBuf::from("tcp/stream.rs"),
+            PathBuf::from("tcp/stream/buf/mod.rs"),
+            PathBuf::from("udp/mod.rs"),
+            PathBuf::from("udp/socket.rs"),
+        ]
+    );
+}
+
+#[test]
+fn scope_item_lookup() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Foo").field("a", "u8");
+    scope.new_enum("Kind").new_variant("A");
+    scope.new_fn("helper");
+    scope.new_trait("Named");
+    scope
+        .new_impl("Foo")
+        .new_fn("new")
+        .ret("Self")
+        .line("Foo { a: 0 }");
+    scope.new_impl("Foo").generic("T").impl_trait("From<T>");
+    scope.new_impl("Bar");
+
+    // A later pass adds to items created earlier
+    scope
+        .get_struct_mut("Foo")
+        .unwrap()
+        .derive("Debug")
+        .field("b", "u16");
+    scope.get_enum_mut("Kind").unwrap().new_variant("B");
+
+    assert!(scope.get_trait("Named").is_some());
+    assert!(scope.get_struct("Kind").is_none());
+    assert_eq!(scope.impls_for("Foo").count(), 2);
+
+    for imp in scope.impls_for_mut("Foo") {
+        imp.attr("automatically_derived");
+    }
+
+    assert_eq!(scope.remove_fn("helper").unwrap().name(), "helper");
+    assert!(scope.remove_fn("helper").is_none());
+
+    let mut named = Trait::new("Named");
+    named.vis("pub");
+    assert!(scope.replace_trait(named).is_some());
+
+    let expect = r#"
+#[derive(Debug)]
+struct Foo {
+    a: u8,
+    b: u16,
+}
+
+enum Kind {
+    A,
+    B,
+}
+
+pub trait Named {
+}
+
+#[automatically_derived]
+impl Foo {
+    fn new() -> Self {
+        Foo { a: 0 }
+    }
+}
+
+#[automatically_derived]
+impl<T> From<T> for Foo {
+}
+
+impl Bar {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_item_removal_and_replacement() {
+    let mut scope = Scope::new();
+
+    scope.new_union("Bits").field("a", "u32");
+    scope.new_type_alias("Id", "u32");
+    scope.new_const("MAX", "u32").value(10);
+    scope.new_static("COUNT", "u32").value(0);
+    scope.new_macro_rules("noop").new_rule("").line("()");
+    scope.new_extern_block("C").new_fn("abort");
+    scope.new_module("util").new_fn("helper");
+    scope.new_impl("Bits").impl_trait("Clone");
+    scope.new_impl("Bits").impl_trait("Copy");
+    scope.new_impl("Bits").new_fn("zero");
+
+    // Lookups of missing items and items of another kind
+    assert!(scope.get_union("Id").is_none());
+    assert!(scope.get_type_alias_mut("Missing").is_none());
+    assert!(scope.get_extern_block("system").is_none());
+    assert!(scope.get_impl("Bits", Some("Debug")).is_none());
+    assert!(scope.get_impl("Other", None).is_none());
+    assert!(scope.remove_const("COUNT").is_none());
+    assert!(scope.remove_module("helper").is_none());
+    assert!(scope.remove_impl("Bits", Some("Debug")).is_none());
+    assert!(scope.remove_impls_for("Other").is_empty());
+
+    // Removal
+    assert_eq!(scope.remove_static("COUNT").unwrap().name(), "COUNT");
+    assert!(scope.remove_static("COUNT").is_none());
+    assert_eq!(scope.remove_macro_rules("noop").unwrap().name(), "noop");
+    assert_eq!(scope.remove_module("util").unwrap().name, "util");
+    assert!(scope.get_module("util").is_none());
+    assert!(scope.remove_impl("Bits", Some("Copy")).is_some());
+    assert!(scope.get_impl("Bits", Some("Copy")).is_none());
+
+    // Replacement, in place or appended
+    let mut max = Const::new("MAX", "u32");
+    max.vis("pub").value(20);
+    assert!(scope.replace_const(max).is_some());
+
+    let mut id = TypeAlias::new("Id", "u64");
+    id.vis("pub");
+    assert_eq!(
+        scope.replace_type_alias(id).unwrap().target(),
+        &Type::new("u32")
+    );
+
+    let mut block = ExternBlock::new("C");
+    block.new_fn("exit").arg("code", "i32");
+    assert!(scope.replace_extern_block(block).is_some());
+
+    let mut clone = Impl::new("Bits");
+    clone
+        .impl_trait("Clone")
+        .new_fn("clone")
+        .arg_ref_self()
+        .ret("Self")
+        .line("*self");
+    assert!(scope.replace_impl(clone).is_some());
+
+    let mut inherent = Impl::new("Bits");
+    inherent.new_fn("one");
+    assert!(scope.get_impl_mut("Bits", None).is_some());
+    assert!(scope.replace_impl(inherent).is_some());
+
+    assert!(scope.replace_union(Union::new("Other")).is_none());
+    scope.get_union_mut("Other").unwrap().field("b", "u8");
+
+    let expect = r#"
+union Bits {
+    a: u32,
+}
+
+pub type Id = u64;
+
+pub const MAX: u32 = 20;
+
+extern "C" {
+    fn exit(code: i32);
+}
+
+impl Clone for Bits {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Bits {
+    fn one() {
+    }
+}
+
+union Other {
+    b: u8,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    assert_eq!(scope.remove_impls_for("Bits").len(), 2);
+    assert!(scope.get_impl("Bits", None).is_none());
+}
+
+#[test]
+fn find_items_by_path() {
+    let mut scope = Scope::new();
+
+    scope.new_fn("main");
+
+    let models = scope
+        .new_module("api")
+        .new_module("v1")
+        .new_module("models");
+    models.new_struct("User").field("id", "u64");
+    models.new_impl("User");
+
+    match scope.find_mut("api::v1::models::User") {
+        Some(ItemMut::Struct(s)) => {
+            s.derive("Debug").field("name", "String");
+        }
+        _ => panic!("expected struct"),
+    }
+
+    match scope.find("api::v1") {
+        Some(ItemRef::Module(m)) => assert_eq!(m.name, "v1"),
+        _ => panic!("expected module"),
+    }
+
+    assert!(scope.find("main").is_some());
+    assert!(scope.find("api::v2::models::User").is_none());
+    assert!(scope.find("api::v1::models::Missing").is_none());
+
+    let paths: Vec<_> = scope
+        .walk()
+        .into_iter()
+        .map(|(path, item)| (path, item.name()))
+        .collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            ("".to_string(), Some("main")),
+            ("".to_string(), Some("api")),
+            ("api".to_string(), Some("v1")),
+            ("api::v1".to_string(), Some("models")),
+            ("api::v1::models".to_string(), Some("User")),
+            ("api::v1::models".to_string(), None),
+        ]
+    );
+
+    let expect = r#"
+#[derive(Debug)]
+struct User {
+    id: u64,
+    name: String,
+}
+"#;
+
+    let user = scope
+        .get_module_mut("api")
+        .unwrap()
+        .get_module_mut("v1")
+        .unwrap()
+        .get_module_mut("models")
+        .unwrap()
+        .scope()
+        .get_struct("User")
+        .unwrap();
+
+    let mut dst = String::new();
+    user.fmt(&mut Formatter::new(&mut dst)).unwrap();
+
+    assert_eq!(dst, &expect[1..]);
+}
+
+#[test]
+fn find_missing_items() {
+    let mut scope = Scope::new();
+
+    scope.new_fn("main");
+    scope.new_impl("Main");
+    scope.new_module("api").new_struct("User");
+
+    assert!(scope.find("").is_none());
+    assert!(scope.find("api::").is_none());
+    assert!(scope.find("::api").is_none());
+    assert!(scope.find("Missing").is_none());
+    assert!(scope.find("main::User").is_none());
+    assert!(scope.find("api::User::id").is_none());
+    assert!(scope.find("Main").is_none());
+    assert!(scope.find_mut("api::Missing").is_none());
+    assert!(scope.find_mut("missing::User").is_none());
+    assert!(scope.find_mut("api::User").is_some());
+
+    assert!(Scope::new().walk().is_empty());
+}
+
+#[test]
+fn use_trees() {
+    let mut scope = Scope::new();
+
+    scope.extern_crate("alloc");
+    scope.extern_crate_as("std", "core").vis("pub");
+
+    scope
+        .import_tree("std::{self, fmt::{Debug, Display}, io::*}")
+        .import_tree("std::collections::HashMap");
+    scope.import_as("std", "fmt::Write", "_");
+    scope.import_as("std::io", "Result", "IoResult");
+    scope.import_tree("serde::{self as serde_crate}");
+    scope.import_tree("tokio");
+    scope.reexport_tree("pub", "self::types::{Request, Response as Resp}");
+    scope.import("bar", "Bar").vis("pub(crate)");
+
+    let expect = r#"
+extern crate alloc;
+pub extern crate std as core;
+use std::{self, fmt::{Debug, Display, Write as _}, io::*};
+use std::collections::HashMap;
+use std::io::Result as IoResult;
+use serde::{self as serde_crate};
+use tokio;
+pub use self::types::{Request, Response as Resp};
+pub(crate) use bar::Bar;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn reexports_keep_private_imports() {
+    let mut scope = Scope::new();
+
+    scope.import("std::fmt", "Debug");
+    scope.reexport_tree("pub", "std::fmt::Debug");
+    scope.reexport_tree("pub", "std::fmt::Debug");
+    scope.import_tree("a::b::{C, D}");
+    scope.import_tree("a::b::{self, E}");
+
+    let expect = r#"
+use std::fmt::Debug;
+use a::b::{self, C, D, E};
+pub use std::fmt::Debug;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn import_grouping_and_granularity() {
+    let mut scope = Scope::new();
+
+    scope.group_imports(GroupImports::StdExternalCrate);
+    scope.import("crate::model", "User");
+    scope.import("serde", "Serialize");
+    scope.import("std::io", "Write");
+    scope.import("std::fmt", "Debug");
+    scope.import("std::fmt", "Display");
+    scope.import("super", "Config");
+    scope.import("std::collections", "HashMap");
+    scope.import_tree("tokio");
+
+    let module = scope.new_module("inner");
+    module.import("std::io", "Read");
+    module.import("std::io", "BufRead");
+    module.import("std", "fmt");
+    module
+        .scope()
+        .imports_granularity(ImportsGranularity::Crate);
+
+    let expect = r#"
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::io::Write;
+
+use serde::Serialize;
+use tokio;
+
+use super::Config;
+use crate::model::User;
+
+mod inner {
+    use std::{fmt, io::{BufRead, Read}};
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.imports_granularity(ImportsGranularity::Item);
+    assert!(scope.to_string().starts_with(
+        "use std::collections::HashMap;\nuse std::fmt::Debug;\nuse std::fmt::Display;\n"
+    ));
+
+    scope.imports_granularity(ImportsGranularity::Module);
+    assert!(scope
+        .to_string()
+        .contains("use std::fmt::{Debug, Display};\n"));
+
+    scope
+        .group_imports(GroupImports::One)
+        .imports_granularity(ImportsGranularity::One);
+    assert!(scope.to_string().starts_with(
+        "use {super::Config, crate::model::User, serde::Serialize, std::{collections::HashMap, \
+         fmt::{Debug, Display}, io::Write}, tokio};\n"
+    ));
+}
+
+#[test]
+fn import_settings_variants() {
+    fn imports(group: GroupImports, granularity: ImportsGranularity) -> String {
+        let mut scope = Scope::new();
+
+        scope.group_imports(group).imports_granularity(granularity);
+        scope.import("crate::model", "User");
+        scope.import("std::io", "Write");
+        scope.import("serde", "Serialize");
+        scope.import("std::fmt", "Display");
+        scope.import("std::fmt", "Debug");
+        scope.import("std", "io");
+        scope.to_string()
+    }
+
+    use self::GroupImports as G;
+    use self::ImportsGranularity as I;
+
+    let expect = r#"
+use crate::model::User;
+use std::io::Write;
+use serde::Serialize;
+use std::fmt::{Display, Debug};
+use std::io;"#;
+
+    assert_eq!(imports(G::Preserve, I::Preserve), &expect[1..]);
+
+    let expect = r#"
+use crate::model::User;
+use serde::Serialize;
+use std::fmt::{Debug, Display};
+use std::io;
+use std::io::Write;"#;
+
+    assert_eq!(imports(G::One, I::Preserve), &expect[1..]);
+
+    let expect = r#"
+use std::fmt::{Debug, Display};
+use std::io;
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::model::User;"#;
+
+    assert_eq!(imports(G::StdExternalCrate, I::Preserve), &expect[1..]);
+
+    let expect = r#"
+use crate::model::User;
+use serde::Serialize;
+use std::{fmt::{Debug, Display}, io::{self, Write}};"#;
+
+    assert_eq!(imports(G::Preserve, I::Crate), &expect[1..]);
+
+    let expect = r#"
+use crate::model::User;
+use serde::Serialize;
+use std::fmt::{Debug, Display};
+use std::io;
+use std::io::Write;"#;
+
+    assert_eq!(imports(G::One, I::Module), &expect[1..]);
+
+    let expect = r#"
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::io;
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::model::User;"#;
+
+    assert_eq!(imports(G::StdExternalCrate, I::Item), &expect[1..]);
+
+    let expect = r#"
+use {crate::model::User, serde::Serialize, std::{fmt::{Debug, Display}, io::{self, Write}}};"#;
+
+    assert_eq!(imports(G::One, I::One), &expect[1..]);
+}
+
+#[test]
+fn file_modules_inherit_import_settings() {
+    let mut scope = Scope::new();
+
+    scope.imports_granularity(ImportsGranularity::Crate);
+
+    let module = scope.new_module("client").set_inline(false);
+    module.import("std::io", "Read");
+    module.import("std::fmt", "Debug");
+
+    let files = scope.to_files("lib.rs");
+
+    assert_eq!(files[1].0, std::path::Path::new("client.rs"));
+    assert_eq!(files[1].1, "use std::{fmt::Debug, io::Read};\n");
+}
+
+#[test]
+fn auto_import_qualified_types() {
+    let mut scope = Scope::new();
+
+    scope.set_auto_import(true);
+    scope.import("std::sync", "Arc");
+
+    scope
+        .new_struct("Cache")
+        .field(
+            "map",
+            "std::collections::HashMap<String, std::sync::Arc<Entry>>",
+        )
+        .field("fmt_result", "std::fmt::Result")
+        .field("io_result", "std::io::Result<()>")
+        .field("entry", "crate::entry::Entry");
+
+    scope.new_struct("Entry");
+
+    scope
+        .new_impl("Cache")
+        .impl_trait("std::fmt::Debug")
+        .new_fn("fmt")
+        .arg_ref_self()
+        .arg("f", "&mut std::fmt::Formatter<'_>")
+        .ret("std::fmt::Result")
+        .line("f.write_str(\"Cache\")");
+
+    scope
+        .new_module("inner")
+        .new_fn("index")
+        .ret("std::collections::BTreeMap<u32, Self::Item>");
+
+    let expect = r#"
+use std::sync::Arc;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+struct Cache {
+    map: HashMap<String, Arc<Entry>>,
+    fmt_result: std::fmt::Result,
+    io_result: std::io::Result<()>,
+    entry: crate::entry::Entry,
+}
+
+struct Entry;
+
+impl Debug for Cache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Cache")
+    }
+}
+
+mod inner {
+    use std::collections::BTreeMap;
+
+    fn index() -> BTreeMap<u32, Self::Item> {
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn auto_import_shadowed_names() {
+    let mut scope = Scope::new();
+
+    scope.set_auto_import(true);
+
+    scope
+        .new_struct("A")
+        .generic("E")
+        .field("a", "std::fmt::Result")
+        .field("b", "Result<u8, E>");
+
+    scope
+        .new_struct("B")
+        .generic("Error")
+        .field("a", "std::io::Error")
+        .field("b", "Option<Error>");
+
+    scope
+        .new_fn("show")
+        .generic("Display")
+        .arg("value", "&dyn std::fmt::Display")
+        .arg("items", "std::vec::Vec<Display>");
+
+    let expect = r#"
+use std::vec::Vec;
+
+struct A<E> {
+    a: std::fmt::Result,
+    b: Result<u8, E>,
+}
+
+struct B<Error> {
+    a: std::io::Error,
+    b: Option<Error>,
+}
+
+fn show<Display>(value: &dyn std::fmt::Display, items: Vec<Display>) {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn auto_import_nested_modules() {
+    let mut scope = Scope::new();
+
+    scope.set_auto_import(true);
+    scope
+        .new_fn("open")
+        .ret("std::collections::HashMap<u32, std::io::Error>");
+
+    let outer = scope.new_module("outer");
+    outer.new_struct("HashMap");
+    outer
+        .new_fn("map")
+        .ret("std::collections::HashMap<u32, u32>");
+
+    let inner = outer.new_module("inner");
+    inner.new_fn("error").ret("std::io::Error");
+
+    scope
+        .new_module("manual")
+        .scope()
+        .set_auto_import(false)
+        .new_fn("error")
+        .ret("std::io::Error");
+
+    let expect = r#"
+use std::collections::HashMap;
+use std::io::Error;
+
+fn open() -> HashMap<u32, Error> {
+}
+
+mod outer {
+    struct HashMap;
+
+    fn map() -> std::collections::HashMap<u32, u32> {
+    }
+
+    mod inner {
+        use std::io::Error;
+
+        fn error() -> Error {
+        }
+    }
+}
+
+mod manual {
+    fn error() -> std::io::Error {
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}